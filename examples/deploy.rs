@@ -2,11 +2,8 @@
 //! interacting with it.
 //! Based on the example from the `alloy` book.
 
-use alloy::{network::ReceiptResponse, primitives::U256, providers::Provider as _, sol};
-use alloy_zksync::{
-    network::transaction_request::TransactionRequest,
-    provider::{ProviderBuilderExt as _, zksync_provider},
-};
+use alloy::{primitives::U256, sol};
+use alloy_zksync::provider::{ContractFactory, ProviderBuilderExt as _, zksync_provider};
 use anyhow::Result;
 
 sol! {
@@ -37,12 +34,9 @@ async fn main() -> Result<()> {
 
     // Manually deploy contract.
     let bytecode = hex::decode("0000008003000039000000400030043f0000000100200190000000180000c13d00000060021002700000000f02200197000000040020008c000000330000413d000000000301043b000000e003300270000000110030009c000000270000613d000000120030009c000000200000613d000000130030009c000000330000c13d000000240020008c000000330000413d0000000002000416000000000002004b000000330000c13d0000000401100370000000000101043b000000350000013d0000000001000416000000000001004b000000330000c13d0000002001000039000001000010044300000120000004430000001001000041000000390001042e0000000001000416000000000001004b000000330000c13d000000000100041a000000800010043f0000001601000041000000390001042e0000000001000416000000000001004b000000330000c13d000000000100041a000000010110003a000000350000c13d0000001401000041000000000010043f0000001101000039000000040010043f00000015010000410000003a0001043000000000010000190000003a00010430000000000010041b0000000001000019000000390001042e0000003800000432000000390001042e0000003a00010430000000000000000000000000000000000000000000000000000000000000000000000000ffffffff000000020000000000000000000000000000004000000100000000000000000000000000000000000000000000000000000000000000000000000000d09de08a000000000000000000000000000000000000000000000000000000008381f58a000000000000000000000000000000000000000000000000000000003fb5c1cb4e487b7100000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002400000000000000000000000000000000000000000000000000000000000000200000008000000000000000000000000000000000000000000000000000000000000000000000000000000000907744cfcba9c9276da62757037b7ad91caa65b463857bae5ffcd6ceb985e728").unwrap();
-    let tx = TransactionRequest::default().with_create_params(bytecode, Vec::new(), Vec::new())?;
-    let receipt = provider.send_transaction(tx).await?.get_receipt().await?;
-
-    let contract_address = receipt
-        .contract_address()
-        .expect("Failed to get contract address");
+    let contract_address = ContractFactory::new(bytecode)
+        .deploy_address(&provider)
+        .await?;
     let contract = Counter::new(contract_address, &provider);
 
     println!("Deployed contract at address: {}", contract.address());
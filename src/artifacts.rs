@@ -0,0 +1,528 @@
+//! Loaders for the contract artifacts produced by `zksolc`.
+//!
+//! Supported formats are:
+//! - hardhat-zksync artifacts (`artifacts-zk/**/<Contract>.json`);
+//! - foundry-zksync artifacts (`zkout/<File>.sol/<Contract>.json`);
+//! - raw standard JSON output of `zksolc`.
+//!
+//! Contracts that deploy other contracts require the bytecodes of the latter to be provided as
+//! factory dependencies. Use [`Artifacts`] to resolve them from the sibling artifacts.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use alloy::{
+    json_abi::JsonAbi,
+    primitives::{B256, Bytes},
+};
+use serde::Deserialize;
+
+use crate::network::{
+    transaction_request::TransactionRequest,
+    unsigned_tx::eip712::{BytecodeHashError, hash_bytecode},
+};
+
+/// Errors that can occur while loading artifacts.
+#[derive(Debug, thiserror::Error)]
+pub enum ArtifactError {
+    /// Artifact file cannot be read.
+    #[error("Failed to read {}: {source}", path.display())]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    /// Artifact is not a valid JSON of the supported formats.
+    #[error("Failed to parse artifact: {0}")]
+    Json(#[from] serde_json::Error),
+    /// Artifact doesn't contain EraVM bytecode, e.g. it belongs to an interface.
+    #[error("Artifact doesn't contain bytecode")]
+    MissingBytecode,
+    /// Artifact bytecode is not a valid hex string, e.g. it contains unlinked libraries.
+    #[error("Artifact contains invalid bytecode: {0}")]
+    InvalidBytecode(#[from] alloy::hex::FromHexError),
+    /// Factory dependency hash is not a valid hex string.
+    #[error("Invalid factory dependency hash: {0}")]
+    InvalidFactoryDependency(String),
+    /// Bytecode of the contract cannot be hashed, e.g. it is not aligned to 32-byte words.
+    #[error(transparent)]
+    BytecodeHash(#[from] BytecodeHashError),
+    /// Factory dependency cannot be found among the loaded artifacts.
+    #[error("Factory dependency {name} with bytecode hash {hash} was not found")]
+    MissingFactoryDependency { hash: B256, name: String },
+    /// Contract cannot be found among the loaded artifacts.
+    #[error("Contract {0} was not found")]
+    ContractNotFound(String),
+}
+
+/// Contract compiled by `zksolc`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ZksolcArtifact {
+    /// Name of the contract, if known.
+    pub contract_name: Option<String>,
+    /// Path of the contract source file, if known.
+    pub source_name: Option<String>,
+    /// ABI of the contract.
+    pub abi: JsonAbi,
+    /// EraVM bytecode of the contract.
+    pub bytecode: Bytes,
+    /// Factory dependencies of the contract, mapping the bytecode hash of the dependency
+    /// to its fully qualified name (`path/to/File.sol:Contract`).
+    pub factory_dependencies: BTreeMap<B256, String>,
+}
+
+impl ZksolcArtifact {
+    /// Parses the artifact in either hardhat-zksync or foundry-zksync format.
+    pub fn from_json(json: &str) -> Result<Self, ArtifactError> {
+        serde_json::from_str::<RawArtifact>(json)?.try_into()
+    }
+
+    /// Loads the artifact in either hardhat-zksync or foundry-zksync format from the file.
+    ///
+    /// If the artifact doesn't specify the contract name (which is the case for foundry-zksync),
+    /// it is derived from the file name.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, ArtifactError> {
+        let path = path.as_ref();
+        let raw = serde_json::from_str(&read_file(path)?)?;
+        Self::from_raw_file(path, raw)
+    }
+
+    fn from_raw_file(path: &Path, raw: RawArtifact) -> Result<Self, ArtifactError> {
+        let mut artifact = Self::try_from(raw)?;
+        if artifact.contract_name.is_none() {
+            artifact.contract_name = path
+                .file_stem()
+                .map(|name| name.to_string_lossy().into_owned());
+        }
+        Ok(artifact)
+    }
+
+    /// Returns the bytecode hash of the contract, as used by ZKsync.
+    pub fn bytecode_hash(&self) -> Result<B256, BytecodeHashError> {
+        hash_bytecode(&self.bytecode).map(B256::from)
+    }
+
+    /// Returns the fully qualified name of the contract (`path/to/File.sol:Contract`), if known.
+    pub fn fully_qualified_name(&self) -> Option<String> {
+        Some(format!(
+            "{}:{}",
+            self.source_name.as_ref()?,
+            self.contract_name.as_ref()?
+        ))
+    }
+}
+
+/// Collection of artifacts, used to resolve factory dependencies of the contracts.
+#[derive(Debug, Clone, Default)]
+pub struct Artifacts {
+    artifacts: Vec<ZksolcArtifact>,
+}
+
+impl Artifacts {
+    /// Creates an empty collection.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads all the artifacts from the directory, e.g. `artifacts-zk` or `zkout`, recursively.
+    ///
+    /// Files that are not contract artifacts (e.g. build info or artifacts of interfaces)
+    /// are skipped, while malformed JSON files and artifacts result in an error.
+    pub fn from_dir(path: impl AsRef<Path>) -> Result<Self, ArtifactError> {
+        let mut this = Self::new();
+        this.load_dir(path.as_ref())?;
+        Ok(this)
+    }
+
+    /// Loads all the contracts from the standard JSON output of `zksolc`.
+    ///
+    /// Contracts without bytecode (e.g. interfaces) are skipped.
+    pub fn from_standard_json(json: &str) -> Result<Self, ArtifactError> {
+        let output: StandardJsonOutput = serde_json::from_str(json)?;
+        let mut this = Self::new();
+        for (source_name, contracts) in output.contracts {
+            for (contract_name, raw) in contracts {
+                let mut artifact = match ZksolcArtifact::try_from(raw) {
+                    Err(ArtifactError::MissingBytecode) => continue,
+                    result => result?,
+                };
+                artifact.source_name = Some(source_name.clone());
+                artifact.contract_name = Some(contract_name);
+                this.insert(artifact);
+            }
+        }
+        Ok(this)
+    }
+
+    /// Adds the artifact to the collection.
+    pub fn insert(&mut self, artifact: ZksolcArtifact) {
+        self.artifacts.push(artifact);
+    }
+
+    /// Returns an iterator over the artifacts.
+    pub fn iter(&self) -> impl Iterator<Item = &ZksolcArtifact> {
+        self.artifacts.iter()
+    }
+
+    /// Looks up the artifact by either its fully qualified name (`path/to/File.sol:Contract`)
+    /// or the contract name.
+    pub fn get(&self, name: &str) -> Option<&ZksolcArtifact> {
+        self.artifacts
+            .iter()
+            .find(|artifact| artifact.fully_qualified_name().as_deref() == Some(name))
+            .or_else(|| {
+                self.artifacts
+                    .iter()
+                    .find(|artifact| artifact.contract_name.as_deref() == Some(name))
+            })
+    }
+
+    /// Looks up the artifact by its bytecode hash.
+    pub fn get_by_bytecode_hash(&self, hash: B256) -> Option<&ZksolcArtifact> {
+        self.artifacts
+            .iter()
+            .find(|artifact| artifact.bytecode_hash().ok() == Some(hash))
+    }
+
+    /// Resolves the bytecodes of all the factory dependencies of the artifact, including
+    /// the dependencies of the dependencies.
+    pub fn resolve_factory_deps(
+        &self,
+        artifact: &ZksolcArtifact,
+    ) -> Result<Vec<Bytes>, ArtifactError> {
+        let mut visited = BTreeSet::from([artifact.bytecode_hash()?]);
+        let mut pending: Vec<_> = artifact.factory_dependencies.iter().collect();
+        let mut factory_deps = Vec::new();
+        while let Some((hash, name)) = pending.pop() {
+            if !visited.insert(*hash) {
+                continue;
+            }
+            let dependency = self.get_by_bytecode_hash(*hash).ok_or_else(|| {
+                ArtifactError::MissingFactoryDependency {
+                    hash: *hash,
+                    name: name.clone(),
+                }
+            })?;
+            factory_deps.push(dependency.bytecode.clone());
+            pending.extend(dependency.factory_dependencies.iter());
+        }
+        Ok(factory_deps)
+    }
+
+    /// Builds a transaction request deploying the contract with the given name via `create`,
+    /// with the factory dependencies resolved from the collection.
+    ///
+    /// See [`TransactionRequest::with_create_params`].
+    pub fn create_request(
+        &self,
+        name: &str,
+        constructor_data: Vec<u8>,
+    ) -> Result<TransactionRequest, ArtifactError> {
+        let artifact = self
+            .get(name)
+            .ok_or_else(|| ArtifactError::ContractNotFound(name.to_owned()))?;
+        let factory_deps = self
+            .resolve_factory_deps(artifact)?
+            .into_iter()
+            .map(Into::into)
+            .collect();
+        Ok(TransactionRequest::default().with_create_params(
+            artifact.bytecode.to_vec(),
+            constructor_data,
+            factory_deps,
+        )?)
+    }
+
+    fn load_dir(&mut self, path: &Path) -> Result<(), ArtifactError> {
+        let entries = std::fs::read_dir(path).map_err(|source| ArtifactError::Io {
+            path: path.to_owned(),
+            source,
+        })?;
+        for entry in entries {
+            let path = entry
+                .map_err(|source| ArtifactError::Io {
+                    path: path.to_owned(),
+                    source,
+                })?
+                .path();
+            if path.is_dir() {
+                self.load_dir(&path)?;
+                continue;
+            }
+            let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+            // Hardhat stores debug info next to the artifacts.
+            if !file_name.ends_with(".json") || file_name.ends_with(".dbg.json") {
+                continue;
+            }
+            // Only the files with bytecode are artifacts, others (e.g. build info) are skipped.
+            let json: serde_json::Value = serde_json::from_str(&read_file(&path)?)?;
+            if json.get("bytecode").is_none() && json.get("evm").is_none() {
+                continue;
+            }
+            match ZksolcArtifact::from_raw_file(&path, serde_json::from_value(json)?) {
+                Ok(mut artifact) => {
+                    // Foundry stores artifacts in the directories named after the source files.
+                    if artifact.source_name.is_none() {
+                        artifact.source_name = path
+                            .parent()
+                            .and_then(Path::file_name)
+                            .map(|name| name.to_string_lossy().into_owned());
+                    }
+                    self.insert(artifact);
+                }
+                Err(ArtifactError::MissingBytecode) => continue,
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(())
+    }
+}
+
+fn read_file(path: &Path) -> Result<String, ArtifactError> {
+    std::fs::read_to_string(path).map_err(|source| ArtifactError::Io {
+        path: path.to_owned(),
+        source,
+    })
+}
+
+/// Union of the artifact formats supported by the crate.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawArtifact {
+    #[serde(default)]
+    contract_name: Option<String>,
+    #[serde(default)]
+    source_name: Option<String>,
+    #[serde(default)]
+    abi: JsonAbi,
+    /// Hex string for hardhat-zksync, object for foundry-zksync.
+    #[serde(default)]
+    bytecode: Option<RawBytecode>,
+    /// Standard JSON output.
+    #[serde(default)]
+    evm: Option<RawEvm>,
+    /// hardhat-zksync.
+    #[serde(default)]
+    factory_deps: BTreeMap<String, String>,
+    /// foundry-zksync and standard JSON output.
+    #[serde(default)]
+    factory_dependencies: BTreeMap<String, String>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawBytecode {
+    Hex(String),
+    Object { object: String },
+}
+
+#[derive(Deserialize)]
+struct RawEvm {
+    #[serde(default)]
+    bytecode: Option<RawBytecode>,
+}
+
+#[derive(Deserialize)]
+struct StandardJsonOutput {
+    #[serde(default)]
+    contracts: BTreeMap<String, BTreeMap<String, RawArtifact>>,
+}
+
+impl TryFrom<RawArtifact> for ZksolcArtifact {
+    type Error = ArtifactError;
+
+    fn try_from(raw: RawArtifact) -> Result<Self, Self::Error> {
+        let bytecode = raw
+            .bytecode
+            .or_else(|| raw.evm.and_then(|evm| evm.bytecode))
+            .map(|bytecode| match bytecode {
+                RawBytecode::Hex(hex) | RawBytecode::Object { object: hex } => hex,
+            })
+            .ok_or(ArtifactError::MissingBytecode)?;
+        let bytecode = Bytes::from(alloy::hex::decode(bytecode)?);
+        if bytecode.is_empty() {
+            return Err(ArtifactError::MissingBytecode);
+        }
+
+        let factory_dependencies = raw
+            .factory_deps
+            .into_iter()
+            .chain(raw.factory_dependencies)
+            .map(|(hash, name)| {
+                B256::from_str(&hash)
+                    .map(|hash| (hash, name))
+                    .map_err(|_| ArtifactError::InvalidFactoryDependency(hash))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            contract_name: raw.contract_name,
+            source_name: raw.source_name,
+            abi: raw.abi,
+            bytecode,
+            factory_dependencies,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::hex;
+
+    fn bytecode(byte: u8) -> Vec<u8> {
+        vec![byte; 32]
+    }
+
+    fn bytecode_hash(byte: u8) -> B256 {
+        hash_bytecode(&bytecode(byte)).unwrap().into()
+    }
+
+    #[test]
+    fn hardhat_artifact() {
+        let json = serde_json::json!({
+            "_format": "hh-zksolc-artifact-1",
+            "contractName": "Factory",
+            "sourceName": "contracts/Factory.sol",
+            "abi": [{ "type": "function", "name": "deploy", "inputs": [], "outputs": [], "stateMutability": "nonpayable" }],
+            "bytecode": format!("0x{}", hex::encode(bytecode(1))),
+            "deployedBytecode": format!("0x{}", hex::encode(bytecode(1))),
+            "linkReferences": {},
+            "deployedLinkReferences": {},
+            "factoryDeps": { hex::encode(bytecode_hash(2)): "contracts/Child.sol:Child" },
+        });
+
+        let artifact = ZksolcArtifact::from_json(&json.to_string()).unwrap();
+        assert_eq!(
+            artifact.fully_qualified_name().as_deref(),
+            Some("contracts/Factory.sol:Factory")
+        );
+        assert!(artifact.abi.function("deploy").is_some());
+        assert_eq!(artifact.bytecode, Bytes::from(bytecode(1)));
+        assert_eq!(artifact.bytecode_hash().unwrap(), bytecode_hash(1));
+        assert_eq!(
+            artifact.factory_dependencies,
+            BTreeMap::from([(bytecode_hash(2), "contracts/Child.sol:Child".to_owned())])
+        );
+    }
+
+    #[test]
+    fn foundry_artifact() {
+        let json = serde_json::json!({
+            "abi": [],
+            "bytecode": { "object": hex::encode(bytecode(1)) },
+            "methodIdentifiers": {},
+            "hash": hex::encode(bytecode_hash(1)),
+            "factoryDependencies": {},
+            "id": 0,
+        });
+
+        let artifact = ZksolcArtifact::from_json(&json.to_string()).unwrap();
+        assert_eq!(artifact.bytecode, Bytes::from(bytecode(1)));
+        assert!(artifact.factory_dependencies.is_empty());
+        assert_eq!(artifact.fully_qualified_name(), None);
+    }
+
+    #[test]
+    fn standard_json_output() {
+        let json = serde_json::json!({
+            "contracts": {
+                "contracts/Factory.sol": {
+                    "Factory": {
+                        "abi": [],
+                        "evm": { "bytecode": { "object": hex::encode(bytecode(1)) } },
+                        "factoryDependencies": {
+                            hex::encode(bytecode_hash(2)): "contracts/Child.sol:Child"
+                        },
+                    },
+                    "IFactory": {
+                        "abi": [],
+                        "evm": { "bytecode": { "object": "" } },
+                    },
+                },
+                "contracts/Child.sol": {
+                    "Child": {
+                        "abi": [],
+                        "evm": { "bytecode": { "object": hex::encode(bytecode(2)) } },
+                        "factoryDependencies": {
+                            hex::encode(bytecode_hash(3)): "contracts/Child.sol:Grandchild"
+                        },
+                    },
+                    "Grandchild": {
+                        "abi": [],
+                        "evm": { "bytecode": { "object": hex::encode(bytecode(3)) } },
+                    },
+                },
+            },
+        });
+
+        let artifacts = Artifacts::from_standard_json(&json.to_string()).unwrap();
+        assert_eq!(artifacts.iter().count(), 3);
+        assert!(artifacts.get("IFactory").is_none());
+
+        let factory = artifacts.get("contracts/Factory.sol:Factory").unwrap();
+        let factory_deps = artifacts.resolve_factory_deps(factory).unwrap();
+        assert_eq!(
+            factory_deps,
+            vec![Bytes::from(bytecode(2)), Bytes::from(bytecode(3))]
+        );
+
+        let request = artifacts.create_request("Factory", vec![]).unwrap();
+        // The contract itself is added to the factory dependencies as well.
+        assert_eq!(request.factory_deps().map(|deps| deps.len()), Some(3));
+
+        let child = artifacts.get("Child").unwrap().clone();
+        let mut partial = Artifacts::new();
+        partial.insert(child.clone());
+        assert!(matches!(
+            partial.resolve_factory_deps(&child),
+            Err(ArtifactError::MissingFactoryDependency { hash, .. }) if hash == bytecode_hash(3)
+        ));
+    }
+
+    #[test]
+    fn load_dir() {
+        let dir =
+            std::env::temp_dir().join(format!("alloy-zksync-artifacts-{}", std::process::id()));
+        let contract_dir = dir.join("Counter.sol");
+        std::fs::create_dir_all(&contract_dir).unwrap();
+        let artifact = serde_json::json!({
+            "abi": [],
+            "bytecode": { "object": hex::encode(bytecode(1)) },
+        });
+        std::fs::write(contract_dir.join("Counter.json"), artifact.to_string()).unwrap();
+        std::fs::write(contract_dir.join("Counter.dbg.json"), "{}").unwrap();
+        std::fs::write(dir.join("build-info.json"), r#"{"input": {}}"#).unwrap();
+
+        let artifacts = Artifacts::from_dir(&dir);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let artifacts = artifacts.unwrap();
+        assert_eq!(artifacts.iter().count(), 1);
+        let counter = artifacts.get("Counter.sol:Counter").unwrap();
+        assert_eq!(counter.bytecode, Bytes::from(bytecode(1)));
+    }
+
+    #[test]
+    fn load_dir_fails_on_malformed_artifact() {
+        let dir = std::env::temp_dir().join(format!(
+            "alloy-zksync-malformed-artifacts-{}",
+            std::process::id()
+        ));
+        let contract_dir = dir.join("Counter.sol");
+        std::fs::create_dir_all(&contract_dir).unwrap();
+        std::fs::write(
+            contract_dir.join("Counter.json"),
+            r#"{"abi": "not an abi", "bytecode": "0x00"}"#,
+        )
+        .unwrap();
+        std::fs::write(contract_dir.join("Broken.json"), r#"{"abi": ["#).unwrap();
+
+        let result = Artifacts::from_dir(&dir);
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert!(matches!(result, Err(ArtifactError::Json(_))));
+    }
+}
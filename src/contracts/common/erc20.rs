@@ -14,6 +14,7 @@ alloy::sol! {
     contract ERC20 {
         function allowance(address owner, address spender) external view returns (uint256);
         function approve(address spender, uint256 value) external returns (bool);
+        function balanceOf(address account) external view returns (uint256);
 
         function name() public view virtual returns (string memory);
         function symbol() public view virtual returns (string memory);
@@ -44,14 +45,27 @@ where
     let erc20_symbol = erc20_contract.symbol().call().await?;
     let erc20_decimals = erc20_contract.decimals().call().await?;
 
-    let token_data = Bytes::from(
+    Ok(encode_token_data(erc20_name, erc20_symbol, erc20_decimals))
+}
+
+/// Encodes the token data for bridging from the token name, symbol and decimals.
+///
+/// # Arguments
+///
+/// * `name` - The name of the token.
+/// * `symbol` - The symbol of the token.
+/// * `decimals` - The decimals of the token.
+///
+/// # Returns
+///
+/// The encoded token data as `Bytes`.
+pub(crate) fn encode_token_data(name: String, symbol: String, decimals: u8) -> Bytes {
+    Bytes::from(
         DynSolValue::Tuple(vec![
-            DynSolValue::Bytes(DynSolValue::String(erc20_name).abi_encode()),
-            DynSolValue::Bytes(DynSolValue::String(erc20_symbol).abi_encode()),
-            DynSolValue::Bytes(DynSolValue::Uint(U256::from(erc20_decimals), 256).abi_encode()),
+            DynSolValue::Bytes(DynSolValue::String(name).abi_encode()),
+            DynSolValue::Bytes(DynSolValue::String(symbol).abi_encode()),
+            DynSolValue::Bytes(DynSolValue::Uint(U256::from(decimals), 256).abi_encode()),
         ])
         .abi_encode_params(),
-    );
-
-    Ok(token_data)
+    )
 }
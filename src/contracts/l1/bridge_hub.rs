@@ -1,3 +1,16 @@
+use alloy::{
+    primitives::{Address, B256, Bytes, Log, U256, keccak256},
+    sol_types::{SolEvent, SolValue},
+};
+
+use crate::{
+    network::{
+        tx_type::TxType,
+        unsigned_tx::eip712::{BytecodeHashError, hash_bytecode},
+    },
+    utils::apply_l1_to_l2_alias,
+};
+
 alloy::sol! {
     /// Represents a direct L2 transaction request.
     #[allow(missing_docs)]
@@ -27,8 +40,19 @@ alloy::sol! {
         bytes secondBridgeCalldata;
     }
 
+    /// Represents the output of the second bridge for an L2 transaction request involving two bridges.
+    #[allow(missing_docs)]
+    struct L2TransactionRequestTwoBridgesInner {
+        bytes32 magicValue;
+        address l2Contract;
+        bytes l2Calldata;
+        bytes[] factoryDeps;
+        bytes32 txDataHash;
+    }
+
     /// Represents a canonical L2 transaction.
     #[allow(missing_docs)]
+    #[derive(Debug, PartialEq, Eq)]
     struct L2CanonicalTransaction {
         uint256 txType;
         uint256 from;
@@ -115,3 +139,320 @@ alloy::sol! {
         );
     }
 }
+
+/// Initiator of an L1 -> L2 transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum L1Account {
+    /// Externally owned account, i.e. the origin of the L1 transaction.
+    Eoa(Address),
+    /// L1 contract. Its address is aliased on L2.
+    Contract(Address),
+}
+
+impl L1Account {
+    /// Returns the address the account has on L2.
+    pub fn l2_address(&self) -> Address {
+        match self {
+            Self::Eoa(address) => *address,
+            Self::Contract(address) => apply_l1_to_l2_alias(*address),
+        }
+    }
+}
+
+impl L2CanonicalTransaction {
+    /// Builds the canonical transaction the same way as the Mailbox does for
+    /// `requestL2TransactionDirect`.
+    ///
+    /// `serial_id` is the ID of the priority operation, i.e. the total number of priority
+    /// transactions of the chain before this one. `l2_gas_price` is the L2 gas price derived by
+    /// the Mailbox, which equals the [base cost](Bridgehub::l2TransactionBaseCostCall) divided
+    /// by the L2 gas limit.
+    ///
+    /// If the refund recipient is not set, the refund goes to the sender. A refund recipient that is
+    /// an L1 contract is aliased by the Mailbox, so in this case `reserved[1]` has to be updated.
+    pub fn from_request_direct(
+        request: &L2TransactionRequestDirect,
+        sender: L1Account,
+        serial_id: U256,
+        l2_gas_price: U256,
+    ) -> Result<Self, BytecodeHashError> {
+        let l2_sender = sender.l2_address();
+        Ok(Self {
+            txType: U256::from(TxType::L1Priority as u8),
+            from: address_to_u256(l2_sender),
+            to: address_to_u256(request.l2Contract),
+            gasLimit: request.l2GasLimit,
+            gasPerPubdataByteLimit: request.l2GasPerPubdataByteLimit,
+            maxFeePerGas: l2_gas_price,
+            maxPriorityFeePerGas: U256::ZERO,
+            paymaster: U256::ZERO,
+            nonce: serial_id,
+            value: request.l2Value,
+            reserved: [
+                request.mintValue,
+                address_to_u256(refund_recipient(request.refundRecipient, l2_sender)),
+                U256::ZERO,
+                U256::ZERO,
+            ],
+            data: request.l2Calldata.clone(),
+            signature: Bytes::new(),
+            factoryDeps: hash_factory_deps(&request.factoryDeps)?,
+            paymasterInput: Bytes::new(),
+            reservedDynamic: Bytes::new(),
+        })
+    }
+
+    /// Builds the canonical transaction the same way as the Mailbox does for
+    /// `requestL2TransactionTwoBridges`.
+    ///
+    /// `bridge_output` is the output of the second bridge for the request, `sender` is the
+    /// initiator of the request on L1. The L2 transaction itself is sent on behalf of the second bridge.
+    /// See [`Self::from_request_direct`] for the rest of the parameters.
+    pub fn from_request_two_bridges(
+        request: &L2TransactionRequestTwoBridges,
+        bridge_output: &L2TransactionRequestTwoBridgesInner,
+        sender: L1Account,
+        serial_id: U256,
+        l2_gas_price: U256,
+    ) -> Result<Self, BytecodeHashError> {
+        Ok(Self {
+            txType: U256::from(TxType::L1Priority as u8),
+            from: address_to_u256(apply_l1_to_l2_alias(request.secondBridgeAddress)),
+            to: address_to_u256(bridge_output.l2Contract),
+            gasLimit: request.l2GasLimit,
+            gasPerPubdataByteLimit: request.l2GasPerPubdataByteLimit,
+            maxFeePerGas: l2_gas_price,
+            maxPriorityFeePerGas: U256::ZERO,
+            paymaster: U256::ZERO,
+            nonce: serial_id,
+            value: request.l2Value,
+            reserved: [
+                request.mintValue,
+                address_to_u256(refund_recipient(
+                    request.refundRecipient,
+                    sender.l2_address(),
+                )),
+                U256::ZERO,
+                U256::ZERO,
+            ],
+            data: bridge_output.l2Calldata.clone(),
+            signature: Bytes::new(),
+            factoryDeps: hash_factory_deps(&bridge_output.factoryDeps)?,
+            paymasterInput: Bytes::new(),
+            reservedDynamic: Bytes::new(),
+        })
+    }
+
+    /// Returns the canonical hash of the transaction, which is also its hash on L2.
+    pub fn hash(&self) -> B256 {
+        keccak256(self.abi_encode())
+    }
+}
+
+/// Priority operation requested on L1, decoded from the `NewPriorityRequest` event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PriorityOp {
+    /// Serial ID of the priority operation.
+    pub serial_id: U256,
+    /// Hash of the L2 transaction.
+    pub tx_hash: B256,
+    /// Timestamp until which the operation has to be processed.
+    pub expiration_timestamp: u64,
+    /// Canonical L2 transaction.
+    pub transaction: L2CanonicalTransaction,
+    /// Bytecodes of the factory dependencies of the transaction.
+    pub factory_deps: Vec<Bytes>,
+}
+
+impl PriorityOp {
+    /// Decodes the priority operation from the `NewPriorityRequest` event log.
+    pub fn decode_log(log: &Log) -> alloy::sol_types::Result<Self> {
+        Bridgehub::NewPriorityRequest::decode_log(log).map(|log| log.data.into())
+    }
+
+    /// Address of the transaction sender on L2.
+    pub fn sender(&self) -> Address {
+        u256_to_address(self.transaction.from)
+    }
+
+    /// Address of the transaction recipient on L2.
+    pub fn contract_address(&self) -> Address {
+        u256_to_address(self.transaction.to)
+    }
+
+    /// Amount of the base token minted on L2 with the transaction.
+    pub fn mint_value(&self) -> U256 {
+        self.transaction.reserved[0]
+    }
+
+    /// Recipient of the refund for the unused gas on L2.
+    pub fn refund_recipient(&self) -> Address {
+        u256_to_address(self.transaction.reserved[1])
+    }
+}
+
+impl From<Bridgehub::NewPriorityRequest> for PriorityOp {
+    fn from(event: Bridgehub::NewPriorityRequest) -> Self {
+        Self {
+            serial_id: event.txId,
+            tx_hash: event.txHash,
+            expiration_timestamp: event.expirationTimestamp,
+            transaction: event.transaction,
+            factory_deps: event.factoryDeps,
+        }
+    }
+}
+
+fn refund_recipient(refund_recipient: Address, l2_sender: Address) -> Address {
+    if refund_recipient == Address::ZERO {
+        l2_sender
+    } else {
+        refund_recipient
+    }
+}
+
+fn hash_factory_deps(factory_deps: &[Bytes]) -> Result<Vec<U256>, BytecodeHashError> {
+    factory_deps
+        .iter()
+        .map(|dep| hash_bytecode(dep).map(U256::from_be_bytes))
+        .collect()
+}
+
+fn address_to_u256(address: Address) -> U256 {
+    U256::from_be_slice(address.as_slice())
+}
+
+fn u256_to_address(value: U256) -> Address {
+    Address::from_word(value.to_be_bytes().into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::{LogData, address};
+
+    fn direct_request() -> L2TransactionRequestDirect {
+        L2TransactionRequestDirect {
+            chainId: U256::from(270),
+            mintValue: U256::from(1_000_000),
+            l2Contract: address!("3333333333333333333333333333333333333333"),
+            l2Value: U256::from(10),
+            l2Calldata: Bytes::from_static(&[1, 2, 3]),
+            l2GasLimit: U256::from(300_000),
+            l2GasPerPubdataByteLimit: U256::from(800),
+            factoryDeps: vec![],
+            refundRecipient: Address::ZERO,
+        }
+    }
+
+    #[test]
+    fn canonical_tx_from_direct_request() {
+        let sender = address!("1111111111111111111111111111111111111111");
+        let tx = L2CanonicalTransaction::from_request_direct(
+            &direct_request(),
+            L1Account::Eoa(sender),
+            U256::from(42),
+            U256::from(250_000_000),
+        )
+        .unwrap();
+
+        assert_eq!(tx.txType, U256::from(TxType::L1Priority as u8));
+        assert_eq!(tx.from, address_to_u256(sender));
+        assert_eq!(tx.nonce, U256::from(42));
+        assert_eq!(tx.maxFeePerGas, U256::from(250_000_000));
+        assert_eq!(tx.reserved[0], U256::from(1_000_000));
+        // Refund goes to the sender if the recipient is not set.
+        assert_eq!(tx.reserved[1], address_to_u256(sender));
+        assert_eq!(tx.hash(), keccak256(tx.abi_encode()));
+    }
+
+    #[test]
+    fn canonical_tx_aliases_contract_sender() {
+        let sender = address!("1111111111111111111111111111111111111111");
+        let tx = L2CanonicalTransaction::from_request_direct(
+            &direct_request(),
+            L1Account::Contract(sender),
+            U256::ZERO,
+            U256::from(250_000_000),
+        )
+        .unwrap();
+
+        let aliased = address!("2222111111111111111111111111111111112222");
+        assert_eq!(u256_to_address(tx.from), aliased);
+        assert_eq!(u256_to_address(tx.reserved[1]), aliased);
+    }
+
+    #[test]
+    fn canonical_tx_from_two_bridges_request() {
+        let second_bridge = address!("4444444444444444444444444444444444444444");
+        let request = L2TransactionRequestTwoBridges {
+            chainId: U256::from(270),
+            mintValue: U256::from(1_000_000),
+            l2Value: U256::ZERO,
+            l2GasLimit: U256::from(300_000),
+            l2GasPerPubdataByteLimit: U256::from(800),
+            refundRecipient: address!("5555555555555555555555555555555555555555"),
+            secondBridgeAddress: second_bridge,
+            secondBridgeValue: U256::ZERO,
+            secondBridgeCalldata: Bytes::new(),
+        };
+        let bridge_output = L2TransactionRequestTwoBridgesInner {
+            magicValue: B256::ZERO,
+            l2Contract: address!("6666666666666666666666666666666666666666"),
+            l2Calldata: Bytes::from_static(&[4, 5, 6]),
+            factoryDeps: vec![],
+            txDataHash: B256::ZERO,
+        };
+        let tx = L2CanonicalTransaction::from_request_two_bridges(
+            &request,
+            &bridge_output,
+            L1Account::Eoa(address!("1111111111111111111111111111111111111111")),
+            U256::from(1),
+            U256::from(250_000_000),
+        )
+        .unwrap();
+
+        assert_eq!(
+            u256_to_address(tx.from),
+            apply_l1_to_l2_alias(second_bridge)
+        );
+        assert_eq!(u256_to_address(tx.to), bridge_output.l2Contract);
+        assert_eq!(tx.data, bridge_output.l2Calldata);
+        assert_eq!(u256_to_address(tx.reserved[1]), request.refundRecipient);
+    }
+
+    #[test]
+    fn decode_priority_op() {
+        let sender = address!("1111111111111111111111111111111111111111");
+        let transaction = L2CanonicalTransaction::from_request_direct(
+            &direct_request(),
+            L1Account::Eoa(sender),
+            U256::from(7),
+            U256::from(250_000_000),
+        )
+        .unwrap();
+        let event = Bridgehub::NewPriorityRequest {
+            txId: U256::from(7),
+            txHash: transaction.hash(),
+            expirationTimestamp: 1_700_000_000,
+            transaction,
+            factoryDeps: vec![],
+        };
+        let log = Log {
+            address: Address::ZERO,
+            data: LogData::from(&event),
+        };
+
+        let op = PriorityOp::decode_log(&log).unwrap();
+        assert_eq!(op.serial_id, U256::from(7));
+        assert_eq!(op.tx_hash, op.transaction.hash());
+        assert_eq!(op.expiration_timestamp, 1_700_000_000);
+        assert_eq!(op.sender(), sender);
+        assert_eq!(
+            op.contract_address(),
+            address!("3333333333333333333333333333333333333333")
+        );
+        assert_eq!(op.mint_value(), U256::from(1_000_000));
+        assert_eq!(op.refund_recipient(), sender);
+    }
+}
@@ -6,6 +6,7 @@ use alloy::{
 alloy::sol! {
      /// L1Bridge contract for interacting with Layer 2 bridges.
     #[sol(rpc)]
+    #[allow(clippy::too_many_arguments)]
     contract L1Bridge {
         /// Retrieves the address of the L2 bridge for a given chain ID.
         ///
@@ -17,6 +18,77 @@ alloy::sol! {
         ///
         /// The address of the L2 bridge.
         function l2BridgeAddress(uint256 _chainId) external view returns (address);
+
+        /// Retrieves the address of the L1Nullifier contract.
+        ///
+        /// Only available since protocol version 26, where the L1 shared bridge is replaced
+        /// by the L1AssetRouter.
+        ///
+        /// # Returns
+        ///
+        /// The address of the L1Nullifier contract.
+        function L1_NULLIFIER() external view returns (address);
+
+        /// Finalizes the withdrawal of funds initiated on Layer 2.
+        ///
+        /// # Arguments
+        ///
+        /// * `_chainId` - The chain ID of the Layer 2 network the withdrawal was initiated on.
+        /// * `_l2BatchNumber` - The L1 batch number where the withdrawal was processed.
+        /// * `_l2MessageIndex` - The position in the L2 logs Merkle tree of the withdrawal message.
+        /// * `_l2TxNumberInBatch` - The L2 transaction number in the batch, in which the withdrawal log was sent.
+        /// * `_message` - The L2 withdrawal message sent via the L1Messenger.
+        /// * `_merkleProof` - The Merkle proof of the inclusion of the withdrawal message.
+        function finalizeWithdrawal(
+            uint256 _chainId,
+            uint256 _l2BatchNumber,
+            uint256 _l2MessageIndex,
+            uint16 _l2TxNumberInBatch,
+            bytes calldata _message,
+            bytes32[] calldata _merkleProof
+        ) external;
+
+        /// Checks whether the withdrawal has already been finalized.
+        ///
+        /// # Arguments
+        ///
+        /// * `_chainId` - The chain ID of the Layer 2 network the withdrawal was initiated on.
+        /// * `_l2BatchNumber` - The L1 batch number where the withdrawal was processed.
+        /// * `_l2MessageIndex` - The position in the L2 logs Merkle tree of the withdrawal message.
+        ///
+        /// # Returns
+        ///
+        /// `true` if the withdrawal was finalized.
+        function isWithdrawalFinalized(
+            uint256 _chainId,
+            uint256 _l2BatchNumber,
+            uint256 _l2MessageIndex
+        ) external view returns (bool);
+
+        /// Withdraws the funds of a deposit that failed to be finalized on Layer 2.
+        ///
+        /// # Arguments
+        ///
+        /// * `_chainId` - The chain ID of the Layer 2 network the deposit was sent to.
+        /// * `_depositSender` - The address of the deposit initiator on Layer 1.
+        /// * `_l1Token` - The address of the deposited token on Layer 1.
+        /// * `_amount` - The amount of the deposit that failed.
+        /// * `_l2TxHash` - The Layer 2 transaction hash of the failed deposit finalization.
+        /// * `_l2BatchNumber` - The L1 batch number where the deposit finalization was processed.
+        /// * `_l2MessageIndex` - The position in the L2 logs Merkle tree of the failed status log.
+        /// * `_l2TxNumberInBatch` - The L2 transaction number in the batch, in which the log was sent.
+        /// * `_merkleProof` - The Merkle proof of the processing of the failed deposit finalization.
+        function claimFailedDeposit(
+            uint256 _chainId,
+            address _depositSender,
+            address _l1Token,
+            uint256 _amount,
+            bytes32 _l2TxHash,
+            uint256 _l2BatchNumber,
+            uint256 _l2MessageIndex,
+            uint16 _l2TxNumberInBatch,
+            bytes32[] calldata _merkleProof
+        ) external;
     }
 }
 
@@ -0,0 +1,52 @@
+alloy::sol! {
+    /// L1Nullifier contract, which tracks the finalized withdrawals since protocol version 26.
+    #[sol(rpc)]
+    contract L1Nullifier {
+        /// Parameters of the withdrawal finalization.
+        ///
+        /// # Fields
+        ///
+        /// * `chainId` - The chain ID of the Layer 2 network the withdrawal was initiated on.
+        /// * `l2BatchNumber` - The L1 batch number where the withdrawal was processed.
+        /// * `l2MessageIndex` - The position in the L2 logs Merkle tree of the withdrawal message.
+        /// * `l2Sender` - The address of the withdrawal message sender on Layer 2.
+        /// * `l2TxNumberInBatch` - The L2 transaction number in the batch, in which the withdrawal log was sent.
+        /// * `message` - The L2 withdrawal message sent via the L1Messenger.
+        /// * `merkleProof` - The Merkle proof of the inclusion of the withdrawal message.
+        struct FinalizeL1DepositParams {
+            uint256 chainId;
+            uint256 l2BatchNumber;
+            uint256 l2MessageIndex;
+            address l2Sender;
+            uint16 l2TxNumberInBatch;
+            bytes message;
+            bytes32[] merkleProof;
+        }
+
+        /// Finalizes the withdrawal of funds initiated on Layer 2.
+        ///
+        /// # Arguments
+        ///
+        /// * `_finalizeWithdrawalParams` - The parameters of the withdrawal finalization.
+        function finalizeDeposit(
+            FinalizeL1DepositParams calldata _finalizeWithdrawalParams
+        ) external;
+
+        /// Checks whether the withdrawal has already been finalized.
+        ///
+        /// # Arguments
+        ///
+        /// * `_chainId` - The chain ID of the Layer 2 network the withdrawal was initiated on.
+        /// * `_l2BatchNumber` - The L1 batch number where the withdrawal was processed.
+        /// * `_l2MessageIndex` - The position in the L2 logs Merkle tree of the withdrawal message.
+        ///
+        /// # Returns
+        ///
+        /// `true` if the withdrawal was finalized.
+        function isWithdrawalFinalized(
+            uint256 _chainId,
+            uint256 _l2BatchNumber,
+            uint256 _l2MessageIndex
+        ) external view returns (bool);
+    }
+}
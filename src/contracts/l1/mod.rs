@@ -5,3 +5,6 @@ pub mod bridge_hub;
 
 /// L1Bridge contract for interacting with Layer 2 bridges.
 pub mod l1_bridge;
+
+/// L1Nullifier contract for finalizing withdrawals since protocol version 26.
+pub mod l1_nullifier;
@@ -9,19 +9,105 @@ pub const CONTRACT_DEPLOYER_ADDRESS: Address = Address::new([
     0x00, 0x00, 0x80, 0x06,
 ]);
 
+pub use self::ContractDeployer::{
+    AccountAbstractionVersion, AccountInfo, AccountNonceOrdering, ContractDeployed,
+    ForceDeployment, create2AccountCall, create2Call, create2EVMCall, createAccountCall,
+    createCall, createEVMCall,
+};
+
 alloy::sol! {
-    /// Function to create a contract.
-    function create(bytes32 salt, bytes32 bytecodeHash, bytes memory constructorInput);
-
-    /// Function to create a contract using create2.
-    function create2(bytes32 salt, bytes32 bytecodeHash, bytes memory constructorInput);
-
-    /// Event emitted when a contract is deployed.
-    event ContractDeployed(
-        address indexed deployerAddress,
-        bytes32 indexed bytecodeHash,
-        address indexed contractAddress
-    );
+    /// ContractDeployer system contract responsible for deploying contracts on ZKsync.
+    #[sol(rpc)]
+    #[allow(missing_docs)]
+    contract ContractDeployer {
+        /// Version of the account abstraction supported by the account.
+        #[derive(Debug, PartialEq, Eq)]
+        enum AccountAbstractionVersion {
+            None,
+            Version1
+        }
+
+        /// Nonce ordering of the account.
+        #[derive(Debug, PartialEq, Eq)]
+        enum AccountNonceOrdering {
+            Sequential,
+            Arbitrary
+        }
+
+        /// Account abstraction information of an address.
+        #[derive(Debug, PartialEq, Eq)]
+        struct AccountInfo {
+            AccountAbstractionVersion supportedAAVersion;
+            AccountNonceOrdering nonceOrdering;
+        }
+
+        /// Deployment performed via `forceDeployOnAddresses`.
+        struct ForceDeployment {
+            bytes32 bytecodeHash;
+            address newAddress;
+            bool callConstructor;
+            uint256 value;
+            bytes input;
+        }
+
+        /// Event emitted when a contract is deployed.
+        event ContractDeployed(
+            address indexed deployerAddress,
+            bytes32 indexed bytecodeHash,
+            address indexed contractAddress
+        );
+
+        /// Event emitted when the nonce ordering of an account is updated.
+        event AccountNonceOrderingUpdated(
+            address indexed accountAddress,
+            AccountNonceOrdering nonceOrdering
+        );
+
+        /// Event emitted when the account abstraction version of an account is updated.
+        event AccountVersionUpdated(
+            address indexed accountAddress,
+            AccountAbstractionVersion aaVersion
+        );
+
+        /// Function to create a contract.
+        function create(bytes32 salt, bytes32 bytecodeHash, bytes memory constructorInput) external payable returns (address newAddress);
+
+        /// Function to create a contract using create2.
+        function create2(bytes32 salt, bytes32 bytecodeHash, bytes memory constructorInput) external payable returns (address newAddress);
+
+        /// Function to create an account.
+        function createAccount(bytes32 salt, bytes32 bytecodeHash, bytes memory constructorInput, AccountAbstractionVersion aaVersion) external payable returns (address newAddress);
+
+        /// Function to create an account using create2.
+        function create2Account(bytes32 salt, bytes32 bytecodeHash, bytes memory constructorInput, AccountAbstractionVersion aaVersion) external payable returns (address newAddress);
+
+        /// Function to create an EVM contract. Only available if the EVM emulator is enabled.
+        function createEVM(bytes memory initCode) external payable returns (uint256 evmGasUsed, address newAddress);
+
+        /// Function to create an EVM contract using create2. Only available if the EVM emulator is enabled.
+        function create2EVM(bytes32 salt, bytes memory initCode) external payable returns (uint256 evmGasUsed, address newAddress);
+
+        /// Returns the address of a contract deployed via create.
+        function getNewAddressCreate(address sender, uint256 senderNonce) external pure returns (address newAddress);
+
+        /// Returns the address of a contract deployed via create2.
+        function getNewAddressCreate2(address sender, bytes32 bytecodeHash, bytes32 salt, bytes memory input) external view returns (address newAddress);
+
+        /// Returns the account abstraction information of an address.
+        function getAccountInfo(address addr) external view returns (AccountInfo memory info);
+
+        /// Returns the account abstraction version of an address, taking EOAs and EVM contracts into account.
+        function extendedAccountVersion(address addr) external view returns (AccountAbstractionVersion);
+
+        /// Updates the account abstraction version of the caller.
+        function updateAccountVersion(AccountAbstractionVersion version) external;
+
+        /// Updates the nonce ordering of the caller.
+        function updateNonceOrdering(AccountNonceOrdering nonceOrdering) external;
+
+        /// Deploys contracts on the provided addresses. Can only be called by the system.
+        function forceDeployOnAddresses(ForceDeployment[] memory deployments) external payable;
+    }
 }
 
 /// Encodes the calldata for creating a contract.
@@ -68,11 +154,69 @@ pub(crate) fn encode_create2_calldata(
     bytecode_hash: B256,
     constructor_input: Bytes,
 ) -> Bytes {
-    let call = createCall {
+    let call = create2Call {
+        salt,
+        bytecodeHash: bytecode_hash,
+        constructorInput: constructor_input,
+    };
+
+    call.abi_encode().into()
+}
+
+/// Encodes the calldata for creating an account.
+///
+/// Same as [`encode_create_calldata`], but additionally marks the deployed contract as an account
+/// supporting the provided account abstraction version.
+pub(crate) fn encode_create_account_calldata(
+    bytecode_hash: B256,
+    constructor_input: Bytes,
+    aa_version: AccountAbstractionVersion,
+) -> Bytes {
+    let call = createAccountCall {
+        salt: Default::default(),
+        bytecodeHash: bytecode_hash,
+        constructorInput: constructor_input,
+        aaVersion: aa_version,
+    };
+
+    call.abi_encode().into()
+}
+
+/// Encodes the calldata for creating an account using create2.
+///
+/// Same as [`encode_create2_calldata`], but additionally marks the deployed contract as an account
+/// supporting the provided account abstraction version.
+pub(crate) fn encode_create2_account_calldata(
+    salt: B256,
+    bytecode_hash: B256,
+    constructor_input: Bytes,
+    aa_version: AccountAbstractionVersion,
+) -> Bytes {
+    let call = create2AccountCall {
         salt,
         bytecodeHash: bytecode_hash,
         constructorInput: constructor_input,
+        aaVersion: aa_version,
     };
 
     call.abi_encode().into()
 }
+
+/// Encodes the calldata for creating an EVM contract.
+pub(crate) fn encode_create_evm_calldata(init_code: Bytes) -> Bytes {
+    createEVMCall {
+        initCode: init_code,
+    }
+    .abi_encode()
+    .into()
+}
+
+/// Encodes the calldata for creating an EVM contract using create2.
+pub(crate) fn encode_create2_evm_calldata(salt: B256, init_code: Bytes) -> Bytes {
+    create2EVMCall {
+        salt,
+        initCode: init_code,
+    }
+    .abi_encode()
+    .into()
+}
@@ -0,0 +1,25 @@
+use alloy::primitives::Address;
+
+/// The address of the known codes storage system contract.
+pub const KNOWN_CODES_STORAGE_ADDRESS: Address = Address::new([
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x80, 0x04,
+]);
+
+alloy::sol! {
+    /// KnownCodesStorage system contract that tracks the bytecode hashes known to the network.
+    /// Only known bytecodes can be deployed.
+    #[sol(rpc)]
+    contract KnownCodesStorage {
+        /// Emitted when the bytecode is marked as known.
+        ///
+        /// # Arguments
+        ///
+        /// * `bytecodeHash` - The hash of the bytecode.
+        /// * `sendBytecodeToL1` - Whether the bytecode was published to L1.
+        event MarkedAsKnown(bytes32 indexed bytecodeHash, bool indexed sendBytecodeToL1);
+
+        /// Returns a non-zero value if the bytecode with the given hash is known.
+        function getMarker(bytes32 _hash) external view returns (uint256);
+    }
+}
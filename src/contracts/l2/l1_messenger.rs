@@ -0,0 +1,22 @@
+use alloy::primitives::Address;
+
+/// The address of the L1 messenger system contract.
+pub const L1_MESSENGER_ADDRESS: Address = Address::new([
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x80, 0x08,
+]);
+
+alloy::sol! {
+    /// L1Messenger system contract that sends arbitrary length messages from Layer 2 to Layer 1.
+    #[sol(rpc)]
+    contract L1Messenger {
+        /// Emitted when a message is sent to Layer 1.
+        ///
+        /// # Arguments
+        ///
+        /// * `_sender` - The address of the message sender on Layer 2.
+        /// * `_hash` - The keccak256 hash of the message.
+        /// * `_message` - The message sent to Layer 1.
+        event L1MessageSent(address indexed _sender, bytes32 indexed _hash, bytes _message);
+    }
+}
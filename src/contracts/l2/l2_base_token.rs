@@ -0,0 +1,51 @@
+use L2BaseToken::withdrawCall;
+use alloy::{
+    primitives::{Address, Bytes},
+    sol_types::SolCall,
+};
+
+/// The address of the L2 base token system contract.
+pub const L2_BASE_TOKEN_ADDRESS: Address = Address::new([
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x80, 0x0a,
+]);
+
+alloy::sol! {
+    /// L2BaseToken system contract that manages the base token balances on Layer 2.
+    #[sol(rpc)]
+    contract L2BaseToken {
+        /// Initiates a withdrawal of the base token to Layer 1.
+        /// The amount to withdraw is passed as the transaction value.
+        ///
+        /// # Arguments
+        ///
+        /// * `_l1Receiver` - The address of the receiver on Layer 1.
+        function withdraw(address _l1Receiver) external payable;
+
+        /// Emitted when a base token withdrawal is initiated.
+        ///
+        /// # Arguments
+        ///
+        /// * `_l2Sender` - The address of the sender on Layer 2.
+        /// * `_l1Receiver` - The address of the receiver on Layer 1.
+        /// * `_amount` - The amount of the base token withdrawn.
+        event Withdrawal(address indexed _l2Sender, address indexed _l1Receiver, uint256 _amount);
+    }
+}
+
+/// Encodes the calldata for withdrawing the base token.
+///
+/// # Arguments
+///
+/// * `receiver` - The address of the receiver on Layer 1.
+///
+/// # Returns
+///
+/// The encoded calldata as `Bytes`.
+pub(crate) fn encode_base_token_withdraw_calldata(receiver: Address) -> Bytes {
+    let call = withdrawCall {
+        _l1Receiver: receiver,
+    };
+
+    call.abi_encode().into()
+}
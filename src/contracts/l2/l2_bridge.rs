@@ -1,4 +1,4 @@
-use L2Bridge::finalizeDepositCall;
+use L2Bridge::{finalizeDepositCall, withdrawCall};
 use alloy::{
     primitives::{Address, Bytes, U256},
     sol_types::SolCall,
@@ -24,6 +24,29 @@ alloy::sol! {
             uint256 _amount,
             bytes calldata _data
         );
+
+        /// Initiates a withdrawal from Layer 2 to Layer 1.
+        ///
+        /// # Arguments
+        ///
+        /// * `_l1Receiver` - The address of the receiver on Layer 1.
+        /// * `_l2Token` - The address of the token on Layer 2.
+        /// * `_amount` - The amount of the token to withdraw.
+        function withdraw(address _l1Receiver, address _l2Token, uint256 _amount);
+
+        /// Returns the address of the Layer 1 token for a given Layer 2 token.
+        ///
+        /// # Arguments
+        ///
+        /// * `_l2Token` - The address of the token on Layer 2.
+        function l1TokenAddress(address _l2Token) external view returns (address);
+
+        /// Returns the address of the Layer 2 token for a given Layer 1 token.
+        ///
+        /// # Arguments
+        ///
+        /// * `_l1Token` - The address of the token on Layer 1.
+        function l2TokenAddress(address _l1Token) external view returns (address);
     }
 }
 
@@ -60,3 +83,28 @@ pub(crate) fn encode_finalize_deposit_calldata(
 
     call.abi_encode().into()
 }
+
+/// Encodes the calldata for withdrawing a token via the L2 bridge.
+///
+/// # Arguments
+///
+/// * `receiver` - The address of the receiver on Layer 1.
+/// * `l2_token_address` - The address of the token on Layer 2.
+/// * `amount` - The amount of the token to withdraw.
+///
+/// # Returns
+///
+/// The encoded calldata as `Bytes`.
+pub(crate) fn encode_withdraw_calldata(
+    receiver: Address,
+    l2_token_address: Address,
+    amount: U256,
+) -> Bytes {
+    let call = withdrawCall {
+        _l1Receiver: receiver,
+        _l2Token: l2_token_address,
+        _amount: amount,
+    };
+
+    call.abi_encode().into()
+}
@@ -3,5 +3,17 @@
 /// Contract deployer utilities.
 pub mod contract_deployer;
 
+/// KnownCodesStorage system contract.
+pub mod known_codes_storage;
+
+/// L1Messenger system contract.
+pub mod l1_messenger;
+
+/// L2BaseToken system contract.
+pub mod l2_base_token;
+
+/// NonceHolder system contract.
+pub mod nonce_holder;
+
 /// L2Bridge contract.
 pub mod l2_bridge;
@@ -0,0 +1,26 @@
+use alloy::primitives::Address;
+
+/// The address of the nonce holder system contract.
+pub const NONCE_HOLDER_ADDRESS: Address = Address::new([
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x80, 0x03,
+]);
+
+alloy::sol! {
+    /// NonceHolder system contract that stores the transaction and deployment nonces of accounts.
+    #[sol(rpc)]
+    contract NonceHolder {
+        /// Returns the current minimal nonce of the account.
+        function getMinNonce(address _address) external view returns (uint256);
+
+        /// Returns the raw version of the nonce, which contains both the transaction
+        /// and the deployment nonces of the account.
+        function getRawNonce(address _address) external view returns (uint256);
+
+        /// Returns the deployment nonce of the account, used to derive `create` addresses.
+        function getDeploymentNonce(address _address) external view returns (uint256 deploymentNonce);
+
+        /// Checks whether the nonce has been used by the account.
+        function isNonceUsed(address _address, uint256 _nonce) external view returns (bool);
+    }
+}
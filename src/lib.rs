@@ -28,6 +28,7 @@
 //! [alloy]: https://github.com/alloy-rs/alloy/
 //! [alloy_network]: https://docs.rs/alloy/latest/alloy/network/trait.Network.html
 
+pub mod artifacts;
 pub mod contracts;
 pub mod network;
 pub mod node_bindings;
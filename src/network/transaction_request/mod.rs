@@ -3,7 +3,9 @@ use alloy::network::{
 };
 use alloy::primitives::{B256, Bytes, TxKind, U256};
 
-use crate::contracts::l2::contract_deployer::CONTRACT_DEPLOYER_ADDRESS;
+use crate::contracts::l2::contract_deployer::{
+    AccountAbstractionVersion, CONTRACT_DEPLOYER_ADDRESS,
+};
 use crate::network::{tx_type::TxType, unsigned_tx::eip712::TxEip712};
 
 use super::unsigned_tx::eip712::{BytecodeHashError, PaymasterParams, hash_bytecode};
@@ -162,6 +164,82 @@ impl TransactionRequest {
             .with_input(input)
             .with_factory_deps(factory_deps))
     }
+
+    /// Builder-pattern method for building a ZKsync EIP-712 transaction deploying an EVM contract.
+    ///
+    /// `init_code` is the EVM creation bytecode with the ABI-encoded constructor arguments appended.
+    /// Requires the EVM emulator to be enabled on the chain.
+    pub fn with_create_evm_params(self, init_code: Vec<u8>) -> Self {
+        let input =
+            crate::contracts::l2::contract_deployer::encode_create_evm_calldata(init_code.into());
+        self.with_to(CONTRACT_DEPLOYER_ADDRESS).with_input(input)
+    }
+
+    /// Builder-pattern method for building a ZKsync EIP-712 transaction deploying an EVM contract
+    /// via create2.
+    ///
+    /// `init_code` is the EVM creation bytecode with the ABI-encoded constructor arguments appended.
+    /// Requires the EVM emulator to be enabled on the chain.
+    pub fn with_create2_evm_params(self, salt: B256, init_code: Vec<u8>) -> Self {
+        let input = crate::contracts::l2::contract_deployer::encode_create2_evm_calldata(
+            salt,
+            init_code.into(),
+        );
+        self.with_to(CONTRACT_DEPLOYER_ADDRESS).with_input(input)
+    }
+
+    /// Builder-pattern method for building a ZKsync EIP-712 transaction deploying a custom account
+    /// via create2.
+    pub fn with_create2_account_params(
+        self,
+        salt: B256,
+        code: Vec<u8>,
+        constructor_data: Vec<u8>,
+        factory_deps: Vec<Vec<u8>>,
+        aa_version: AccountAbstractionVersion,
+    ) -> Result<Self, BytecodeHashError> {
+        let bytecode_hash = hash_bytecode(&code)?;
+        let factory_deps = factory_deps
+            .into_iter()
+            .chain(vec![code])
+            .map(Into::into)
+            .collect();
+        let input = crate::contracts::l2::contract_deployer::encode_create2_account_calldata(
+            salt,
+            bytecode_hash.into(),
+            constructor_data.into(),
+            aa_version,
+        );
+        Ok(self
+            .with_to(CONTRACT_DEPLOYER_ADDRESS)
+            .with_input(input)
+            .with_factory_deps(factory_deps))
+    }
+
+    /// Builder-pattern method for building a ZKsync EIP-712 transaction deploying a custom account.
+    pub fn with_create_account_params(
+        self,
+        code: Vec<u8>,
+        constructor_data: Vec<u8>,
+        factory_deps: Vec<Vec<u8>>,
+        aa_version: AccountAbstractionVersion,
+    ) -> Result<Self, BytecodeHashError> {
+        let bytecode_hash = hash_bytecode(&code)?;
+        let factory_deps = factory_deps
+            .into_iter()
+            .chain(vec![code])
+            .map(Into::into)
+            .collect();
+        let input = crate::contracts::l2::contract_deployer::encode_create_account_calldata(
+            bytecode_hash.into(),
+            constructor_data.into(),
+            aa_version,
+        );
+        Ok(self
+            .with_to(CONTRACT_DEPLOYER_ADDRESS)
+            .with_input(input)
+            .with_factory_deps(factory_deps))
+    }
 }
 
 impl TransactionRequest {
@@ -245,6 +323,11 @@ impl From<crate::network::unsigned_tx::TypedTransaction> for TransactionRequest
                 base: inner.clone().into(),
                 eip_712_meta: inner.eip712_meta,
             },
+            crate::network::unsigned_tx::TypedTransaction::L1Priority(inner)
+            | crate::network::unsigned_tx::TypedTransaction::ProtocolUpgrade(inner) => Self {
+                base: inner.into(),
+                eip_712_meta: None,
+            },
         }
     }
 }
@@ -260,6 +343,11 @@ impl From<crate::network::tx_envelope::TxEnvelope> for TransactionRequest {
                 base: signed.tx().clone().into(),
                 eip_712_meta: signed.tx().clone().eip712_meta,
             },
+            crate::network::tx_envelope::TxEnvelope::L1Priority(sealed)
+            | crate::network::tx_envelope::TxEnvelope::ProtocolUpgrade(sealed) => Self {
+                base: sealed.unseal().into(),
+                eip_712_meta: None,
+            },
         }
     }
 }
@@ -741,4 +829,64 @@ mod tests {
         let result = tx_request.build_unsigned();
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_with_create2_params() {
+        use crate::contracts::l2::contract_deployer::create2Call;
+        use alloy::sol_types::SolCall;
+
+        let salt = B256::repeat_byte(1);
+        let tx_request = TransactionRequest::default()
+            .with_create2_params(salt, vec![0; 32], vec![1, 2, 3], vec![])
+            .unwrap();
+        // `create` has the same arguments, so only the selector tells the calls apart.
+        assert_eq!(tx_request.input().unwrap()[..4], create2Call::SELECTOR);
+        let call = create2Call::abi_decode(tx_request.input().unwrap()).unwrap();
+        assert_eq!(call.salt, salt);
+        assert_eq!(call.constructorInput, Bytes::from(vec![1, 2, 3]));
+        assert_eq!(tx_request.to(), Some(CONTRACT_DEPLOYER_ADDRESS));
+    }
+
+    #[test]
+    fn test_with_create_account_params() {
+        use crate::contracts::l2::contract_deployer::createAccountCall;
+        use alloy::sol_types::SolCall;
+
+        let tx_request = TransactionRequest::default()
+            .with_create_account_params(
+                vec![0; 32],
+                vec![1, 2, 3],
+                vec![],
+                AccountAbstractionVersion::Version1,
+            )
+            .unwrap();
+        let call = createAccountCall::abi_decode(tx_request.input().unwrap()).unwrap();
+        assert_eq!(call.salt, B256::ZERO);
+        assert_eq!(
+            call.bytecodeHash,
+            B256::from(hash_bytecode(&[0; 32]).unwrap())
+        );
+        assert_eq!(call.aaVersion, AccountAbstractionVersion::Version1);
+        assert_eq!(tx_request.factory_deps().map(|deps| deps.len()), Some(1));
+    }
+
+    #[test]
+    fn test_with_create2_account_params() {
+        use crate::contracts::l2::contract_deployer::create2AccountCall;
+        use alloy::sol_types::SolCall;
+
+        let salt = B256::repeat_byte(1);
+        let tx_request = TransactionRequest::default()
+            .with_create2_account_params(
+                salt,
+                vec![0; 32],
+                vec![],
+                vec![],
+                AccountAbstractionVersion::Version1,
+            )
+            .unwrap();
+        let call = create2AccountCall::abi_decode(tx_request.input().unwrap()).unwrap();
+        assert_eq!(call.salt, salt);
+        assert_eq!(call.aaVersion, AccountAbstractionVersion::Version1);
+    }
 }
@@ -93,7 +93,8 @@ impl alloy::consensus::Typed2718 for TransactionResponse {
 
 impl alloy::network::TransactionResponse for TransactionResponse {
     fn tx_hash(&self) -> alloy::primitives::TxHash {
-        self.inner.tx_hash()
+        // Hash of ZKsync-specific transactions is not derived from their EIP-2718 encoding.
+        *self.inner.inner.tx_hash()
     }
 
     fn from(&self) -> alloy::primitives::Address {
@@ -125,50 +126,106 @@ mod serde_from {
     //! Helper module for serializing and deserializing ZKsync [`TransactionResponse`].
     //!
     //! This is needed because we might need to deserialize the `from` field into both
-    //! [`field@alloy::rpc::types::transaction::Transaction::from`] and [`field@TxEip712::from`].
+    //! [`field@alloy::rpc::types::transaction::Transaction::from`] and [`field@TxEip712::from`]
+    //! (or [`field@TxL1::from`] for L1-originated transactions).
     use crate::network::transaction_response::TransactionResponse;
     use crate::network::tx_envelope::TxEnvelope;
     use crate::network::unsigned_tx::eip712::TxEip712;
+    use crate::network::unsigned_tx::l1::{TxL1, sealed_serde};
     use alloy::consensus::{Signed, transaction::Recovered};
-    use alloy::primitives::BlockHash;
+    use alloy::primitives::{Address, BlockHash, Sealed};
     use serde::{Deserialize, Serialize};
 
     /// Exactly the same thing as [`alloy::rpc::types::transaction::Transaction`] but without the
     /// `from` field. We need it because [`TxEnvelope::Eip712`] can consume `from` first thus
     /// failing the entire deserialization process.
     #[derive(Serialize, Deserialize)]
-    pub struct TransactionWithoutFrom {
+    #[serde(rename_all = "camelCase")]
+    pub struct TransactionWithoutFrom<T = Signed<TxEip712>> {
         #[serde(flatten)]
-        pub inner: Signed<TxEip712>,
+        pub inner: T,
+        #[serde(default)]
         pub block_hash: Option<BlockHash>,
+        #[serde(default, with = "alloy::serde::quantity::opt")]
         pub block_number: Option<u64>,
+        #[serde(default, with = "alloy::serde::quantity::opt")]
         pub transaction_index: Option<u64>,
+        // Neither of the inner transactions has its own gas price.
+        #[serde(default, rename = "gasPrice", with = "alloy::serde::quantity::opt")]
         pub effective_gas_price: Option<u128>,
     }
 
+    /// L1-originated transactions, which also have the `from` field.
+    #[derive(Serialize, Deserialize)]
+    #[serde(tag = "type")]
+    pub enum L1TxEnvelope {
+        #[serde(
+            rename = "0xff",
+            serialize_with = "sealed_serde::serialize",
+            deserialize_with = "sealed_serde::deserialize_l1_priority"
+        )]
+        L1Priority(Sealed<TxL1>),
+        #[serde(
+            rename = "0xfe",
+            serialize_with = "sealed_serde::serialize",
+            deserialize_with = "sealed_serde::deserialize_protocol_upgrade"
+        )]
+        ProtocolUpgrade(Sealed<TxL1>),
+    }
+
     /// (De)serializes both regular [`alloy::rpc::types::transaction::Transaction`] and [`TransactionWithoutFrom`].
     #[derive(Serialize, Deserialize)]
     #[serde(untagged)]
     pub enum TransactionEither {
         Regular(alloy::rpc::types::transaction::Transaction<TxEnvelope>),
+        // Must go before `WithoutFrom`, as L1-originated transactions may be deserialized
+        // as `TxEip712` otherwise.
+        L1WithoutFrom(TransactionWithoutFrom<L1TxEnvelope>),
         WithoutFrom(TransactionWithoutFrom),
     }
 
+    impl<T> TransactionWithoutFrom<T> {
+        fn new(value: &TransactionResponse, inner: T) -> Self {
+            Self {
+                inner,
+                block_hash: value.inner.block_hash,
+                block_number: value.inner.block_number,
+                transaction_index: value.inner.transaction_index,
+                effective_gas_price: value.inner.effective_gas_price,
+            }
+        }
+
+        /// Converts into the response, using the sender of the inner transaction as `from`.
+        fn into_response(self, f: impl FnOnce(T) -> (Address, TxEnvelope)) -> TransactionResponse {
+            let (from, inner) = f(self.inner);
+            TransactionResponse {
+                inner: alloy::rpc::types::transaction::Transaction {
+                    inner: Recovered::new_unchecked(inner, from),
+                    block_hash: self.block_hash,
+                    block_number: self.block_number,
+                    transaction_index: self.transaction_index,
+                    effective_gas_price: self.effective_gas_price,
+                },
+            }
+        }
+    }
+
     impl From<TransactionEither> for TransactionResponse {
         fn from(value: TransactionEither) -> Self {
             match value {
                 TransactionEither::Regular(tx) => TransactionResponse { inner: tx },
+                TransactionEither::L1WithoutFrom(value) => {
+                    value.into_response(|inner| match inner {
+                        L1TxEnvelope::L1Priority(sealed) => {
+                            (sealed.from, TxEnvelope::L1Priority(sealed))
+                        }
+                        L1TxEnvelope::ProtocolUpgrade(sealed) => {
+                            (sealed.from, TxEnvelope::ProtocolUpgrade(sealed))
+                        }
+                    })
+                }
                 TransactionEither::WithoutFrom(value) => {
-                    let from = value.inner.tx().from;
-                    TransactionResponse {
-                        inner: alloy::rpc::types::transaction::Transaction {
-                            inner: Recovered::new_unchecked(TxEnvelope::Eip712(value.inner), from),
-                            block_hash: value.block_hash,
-                            block_number: value.block_number,
-                            transaction_index: value.transaction_index,
-                            effective_gas_price: value.effective_gas_price,
-                        },
-                    }
+                    value.into_response(|inner| (inner.tx().from, TxEnvelope::Eip712(inner)))
                 }
             }
         }
@@ -178,16 +235,149 @@ mod serde_from {
         fn from(value: TransactionResponse) -> Self {
             match value.inner.inner.as_ref() {
                 TxEnvelope::Native(_) => TransactionEither::Regular(value.inner),
-                TxEnvelope::Eip712(signed) => {
-                    TransactionEither::WithoutFrom(TransactionWithoutFrom {
-                        inner: signed.clone(),
-                        block_hash: value.inner.block_hash,
-                        block_number: value.inner.block_number,
-                        transaction_index: value.inner.transaction_index,
-                        effective_gas_price: value.inner.effective_gas_price,
-                    })
+                TxEnvelope::Eip712(signed) => TransactionEither::WithoutFrom(
+                    TransactionWithoutFrom::new(&value, signed.clone()),
+                ),
+                TxEnvelope::L1Priority(sealed) => TransactionEither::L1WithoutFrom(
+                    TransactionWithoutFrom::new(&value, L1TxEnvelope::L1Priority(sealed.clone())),
+                ),
+                TxEnvelope::ProtocolUpgrade(sealed) => {
+                    TransactionEither::L1WithoutFrom(TransactionWithoutFrom::new(
+                        &value,
+                        L1TxEnvelope::ProtocolUpgrade(sealed.clone()),
+                    ))
                 }
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+    use crate::network::tx_type::TxType;
+    use alloy::consensus::{Transaction as _, Typed2718 as _};
+    use alloy::network::TransactionResponse as _;
+    use alloy::primitives::{B256, U256, address};
+
+    #[test]
+    fn l1_priority_tx_response() {
+        let tx_json = r#"
+        {
+            "hash": "0x0bd9e3a4db1ee4d9ce5a6c9cbd0b2a5ec4ee6e5fbd3c4b7f8ff8d44e0a1bdd4b",
+            "nonce": "0x2a",
+            "blockHash": "0x5046bdc714b2a9b40e9fbfdfc5140371c1b03b40335d908de92a7686dcc067e9",
+            "blockNumber": "0x1d1551e",
+            "transactionIndex": "0x0",
+            "from": "0x1111111111111111111111111111111111111111",
+            "to": "0x3333333333333333333333333333333333333333",
+            "value": "0xa",
+            "gasPrice": "0xee6b280",
+            "gas": "0x493e0",
+            "input": "0x010203",
+            "v": "0x0",
+            "r": "0x0",
+            "s": "0x0",
+            "type": "0xff",
+            "maxFeePerGas": "0xee6b280",
+            "maxPriorityFeePerGas": "0x0",
+            "chainId": "0x10e",
+            "l1BatchNumber": "0x72ae1",
+            "l1BatchTxIndex": "0x0"
+        }
+        "#;
+
+        let tx = serde_json::from_str::<TransactionResponse>(tx_json).unwrap();
+        assert_eq!(tx.ty(), TxType::L1Priority as u8);
+        assert_eq!(
+            tx.tx_hash(),
+            B256::from_str("0x0bd9e3a4db1ee4d9ce5a6c9cbd0b2a5ec4ee6e5fbd3c4b7f8ff8d44e0a1bdd4b")
+                .unwrap()
+        );
+        assert_eq!(
+            tx.from(),
+            address!("1111111111111111111111111111111111111111")
+        );
+        assert_eq!(tx.nonce(), 42);
+        assert_eq!(tx.gas_limit(), 300000);
+        assert_eq!(tx.value(), U256::from(10));
+        assert_eq!(tx.block_number(), Some(0x1d1551e));
+
+        let envelope: &crate::network::tx_envelope::TxEnvelope = tx.as_ref();
+        assert!(envelope.is_l1_priority());
+        assert_eq!(
+            envelope.as_l1().unwrap().inner().tx_type,
+            TxType::L1Priority
+        );
+
+        let json = serde_json::to_value(&tx).unwrap();
+        assert_eq!(
+            serde_json::from_value::<TransactionResponse>(json).unwrap(),
+            tx
+        );
+    }
+
+    #[test]
+    fn eip712_tx_response() {
+        let tx_json = r#"
+        {
+            "hash": "0xb2adc4d2b3203e186001dc37fdf02cc8e772518425d263adc6a17dbddff3bfda",
+            "nonce": "0x5",
+            "blockHash": "0x5046bdc714b2a9b40e9fbfdfc5140371c1b03b40335d908de92a7686dcc067e9",
+            "blockNumber": "0x1d1551e",
+            "transactionIndex": "0x3",
+            "from": "0x1bc3366b3664c01b8687b1efcfc6478d9351a8a9",
+            "to": "0x9b5def958d0f3b6955cbea4d5b7809b2fb26b059",
+            "value": "0x0",
+            "gasPrice": "0x17d7840",
+            "gas": "0x2b9bcb",
+            "input": "0x010203",
+            "v": "0x1",
+            "r": "0x1",
+            "s": "0x2",
+            "yParity": "0x1",
+            "type": "0x71",
+            "maxFeePerGas": "0x17d7840",
+            "maxPriorityFeePerGas": "0x0",
+            "chainId": "0x144",
+            "gasPerPubdata": "0xc350",
+            "customSignature": null,
+            "paymasterParams": null,
+            "l1BatchNumber": "0x72ae1",
+            "l1BatchTxIndex": "0x469"
+        }
+        "#;
+
+        let tx = serde_json::from_str::<TransactionResponse>(tx_json).unwrap();
+        assert_eq!(tx.ty(), TxType::Eip712 as u8);
+        assert_eq!(
+            tx.tx_hash(),
+            B256::from_str("0xb2adc4d2b3203e186001dc37fdf02cc8e772518425d263adc6a17dbddff3bfda")
+                .unwrap()
+        );
+        assert_eq!(
+            tx.from(),
+            address!("1bc3366b3664c01b8687b1efcfc6478d9351a8a9")
+        );
+        assert_eq!(
+            tx.block_hash(),
+            Some(
+                B256::from_str(
+                    "0x5046bdc714b2a9b40e9fbfdfc5140371c1b03b40335d908de92a7686dcc067e9"
+                )
+                .unwrap()
+            )
+        );
+        assert_eq!(tx.block_number(), Some(0x1d1551e));
+        assert_eq!(tx.transaction_index(), Some(3));
+        assert_eq!(tx.inner.effective_gas_price, Some(25000000));
+
+        let json = serde_json::to_value(&tx).unwrap();
+        assert_eq!(
+            serde_json::from_value::<TransactionResponse>(json).unwrap(),
+            tx
+        );
+    }
+}
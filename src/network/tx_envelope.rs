@@ -1,10 +1,13 @@
-use alloy::consensus::{Signed, Typed2718};
+use alloy::consensus::{Signed, Typed2718, transaction::SignerRecoverable};
 use alloy::network::eip2718::{Decodable2718, Encodable2718};
 use alloy::rlp::{Encodable, Header};
 use serde::{Deserialize, Serialize};
 
 use super::tx_type::TxType;
 use super::unsigned_tx::eip712::TxEip712;
+use super::unsigned_tx::l1::TxL1;
+use alloy::primitives::Sealed;
+
 /// Transaction envelope is a wrapper around the transaction data.
 /// See [`alloy::consensus::TxEnvelope`](https://docs.rs/alloy/latest/alloy/consensus/enum.TxEnvelope.html) for more details.
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -17,15 +20,26 @@ pub enum TxEnvelope {
     Native(alloy::consensus::TxEnvelope),
     /// ZKsync-native EIP712 transaction.
     Eip712(Signed<TxEip712>),
+    /// ZKsync-native L1 priority operation.
+    L1Priority(Sealed<TxL1>),
+    /// ZKsync-native protocol upgrade transaction.
+    ProtocolUpgrade(Sealed<TxL1>),
 }
 
+/// Signature reported for L1-originated transactions, which are not signed.
+pub(crate) const EMPTY_SIGNATURE: alloy::primitives::Signature = alloy::primitives::Signature::new(
+    alloy::primitives::U256::ZERO,
+    alloy::primitives::U256::ZERO,
+    false,
+);
+
 impl TxEnvelope {
     /// Returns true if the transaction is a legacy transaction.
     #[inline]
     pub const fn is_legacy(&self) -> bool {
         match self {
             Self::Native(inner) => inner.is_legacy(),
-            Self::Eip712(_) => false,
+            Self::Eip712(_) | Self::L1Priority(_) | Self::ProtocolUpgrade(_) => false,
         }
     }
 
@@ -34,7 +48,7 @@ impl TxEnvelope {
     pub const fn is_eip2930(&self) -> bool {
         match self {
             Self::Native(inner) => inner.is_eip2930(),
-            Self::Eip712(_) => false,
+            Self::Eip712(_) | Self::L1Priority(_) | Self::ProtocolUpgrade(_) => false,
         }
     }
 
@@ -43,7 +57,7 @@ impl TxEnvelope {
     pub const fn is_eip1559(&self) -> bool {
         match self {
             Self::Native(inner) => inner.is_eip1559(),
-            Self::Eip712(_) => false,
+            Self::Eip712(_) | Self::L1Priority(_) | Self::ProtocolUpgrade(_) => false,
         }
     }
 
@@ -52,7 +66,7 @@ impl TxEnvelope {
     pub const fn is_eip4844(&self) -> bool {
         match self {
             Self::Native(inner) => inner.is_eip4844(),
-            Self::Eip712(_) => false,
+            Self::Eip712(_) | Self::L1Priority(_) | Self::ProtocolUpgrade(_) => false,
         }
     }
 
@@ -61,7 +75,7 @@ impl TxEnvelope {
     pub const fn is_eip7702(&self) -> bool {
         match self {
             Self::Native(inner) => inner.is_eip7702(),
-            Self::Eip712(_) => false,
+            Self::Eip712(_) | Self::L1Priority(_) | Self::ProtocolUpgrade(_) => false,
         }
     }
 
@@ -71,6 +85,18 @@ impl TxEnvelope {
         matches!(self, Self::Eip712(_))
     }
 
+    /// Returns true if the transaction is an L1 priority operation.
+    #[inline]
+    pub const fn is_l1_priority(&self) -> bool {
+        matches!(self, Self::L1Priority(_))
+    }
+
+    /// Returns true if the transaction is a protocol upgrade transaction.
+    #[inline]
+    pub const fn is_protocol_upgrade(&self) -> bool {
+        matches!(self, Self::ProtocolUpgrade(_))
+    }
+
     /// Returns true if the transaction is replay protected.
     ///
     /// All non-legacy transactions are replay protected, as the chain id is
@@ -83,7 +109,7 @@ impl TxEnvelope {
     pub const fn is_replay_protected(&self) -> bool {
         match self {
             Self::Native(inner) => inner.is_replay_protected(),
-            Self::Eip712(_) => true,
+            Self::Eip712(_) | Self::L1Priority(_) | Self::ProtocolUpgrade(_) => true,
         }
     }
 
@@ -91,7 +117,7 @@ impl TxEnvelope {
     pub const fn as_legacy(&self) -> Option<&Signed<alloy::consensus::TxLegacy>> {
         match self {
             Self::Native(inner) => inner.as_legacy(),
-            Self::Eip712(_) => None,
+            Self::Eip712(_) | Self::L1Priority(_) | Self::ProtocolUpgrade(_) => None,
         }
     }
 
@@ -99,7 +125,7 @@ impl TxEnvelope {
     pub const fn as_eip2930(&self) -> Option<&Signed<alloy::consensus::TxEip2930>> {
         match self {
             Self::Native(inner) => inner.as_eip2930(),
-            Self::Eip712(_) => None,
+            Self::Eip712(_) | Self::L1Priority(_) | Self::ProtocolUpgrade(_) => None,
         }
     }
 
@@ -107,7 +133,7 @@ impl TxEnvelope {
     pub const fn as_eip1559(&self) -> Option<&Signed<alloy::consensus::TxEip1559>> {
         match self {
             Self::Native(inner) => inner.as_eip1559(),
-            Self::Eip712(_) => None,
+            Self::Eip712(_) | Self::L1Priority(_) | Self::ProtocolUpgrade(_) => None,
         }
     }
 
@@ -115,7 +141,7 @@ impl TxEnvelope {
     pub const fn as_eip4844(&self) -> Option<&Signed<alloy::consensus::TxEip4844Variant>> {
         match self {
             Self::Native(inner) => inner.as_eip4844(),
-            Self::Eip712(_) => None,
+            Self::Eip712(_) | Self::L1Priority(_) | Self::ProtocolUpgrade(_) => None,
         }
     }
 
@@ -123,31 +149,60 @@ impl TxEnvelope {
     pub const fn as_eip7702(&self) -> Option<&Signed<alloy::consensus::TxEip7702>> {
         match self {
             Self::Native(inner) => inner.as_eip7702(),
-            Self::Eip712(_) => None,
+            Self::Eip712(_) | Self::L1Priority(_) | Self::ProtocolUpgrade(_) => None,
         }
     }
 
     /// Returns the [`TxEip712`] variant if the transaction is an EIP-712 transaction.
     pub const fn as_eip712(&self) -> Option<&Signed<TxEip712>> {
         match self {
-            Self::Native(_) => None,
             Self::Eip712(inner) => Some(inner),
+            Self::Native(_) | Self::L1Priority(_) | Self::ProtocolUpgrade(_) => None,
+        }
+    }
+
+    /// Returns the [`TxL1`] variant if the transaction is an L1-originated transaction.
+    pub const fn as_l1(&self) -> Option<&Sealed<TxL1>> {
+        match self {
+            Self::L1Priority(inner) | Self::ProtocolUpgrade(inner) => Some(inner),
+            Self::Native(_) | Self::Eip712(_) => None,
         }
     }
 
     /// Calculate the signing hash for the transaction.
+    ///
+    /// L1-originated transactions are not signed, so their transaction hash is returned instead.
     pub fn signature_hash(&self) -> alloy::primitives::B256 {
         match self {
             Self::Native(inner) => inner.signature_hash(),
             Self::Eip712(inner) => inner.signature_hash(),
+            Self::L1Priority(inner) | Self::ProtocolUpgrade(inner) => inner.hash(),
+        }
+    }
+
+    /// Recovers the signer of the transaction from its ECDSA signature.
+    ///
+    /// Transactions from custom accounts are authorized by the account itself, so for them
+    /// the recovered address may not match the sender. L1-originated transactions are not signed,
+    /// so their sender is returned as is.
+    pub fn recover_signer(
+        &self,
+    ) -> Result<alloy::primitives::Address, alloy::consensus::crypto::RecoveryError> {
+        match self {
+            Self::Native(inner) => inner.recover_signer(),
+            Self::Eip712(inner) => SignerRecoverable::recover_signer(inner),
+            Self::L1Priority(inner) | Self::ProtocolUpgrade(inner) => Ok(inner.from),
         }
     }
 
     /// Return the reference to signature.
+    ///
+    /// L1-originated transactions are not signed, so an empty signature is returned for them.
     pub const fn signature(&self) -> &alloy::primitives::Signature {
         match self {
             Self::Native(inner) => inner.signature(),
             Self::Eip712(inner) => inner.signature(),
+            Self::L1Priority(_) | Self::ProtocolUpgrade(_) => &EMPTY_SIGNATURE,
         }
     }
 
@@ -157,6 +212,7 @@ impl TxEnvelope {
         match self {
             Self::Native(inner) => inner.tx_hash(),
             Self::Eip712(inner) => inner.hash(),
+            Self::L1Priority(inner) | Self::ProtocolUpgrade(inner) => inner.hash_ref(),
         }
     }
 
@@ -172,6 +228,8 @@ impl TxEnvelope {
                 alloy::consensus::TxType::Eip7702 => crate::network::tx_type::TxType::Eip7702,
             },
             Self::Eip712(_) => crate::network::tx_type::TxType::Eip712,
+            Self::L1Priority(_) => crate::network::tx_type::TxType::L1Priority,
+            Self::ProtocolUpgrade(_) => crate::network::tx_type::TxType::ProtocolUpgrade,
         }
     }
 
@@ -180,6 +238,9 @@ impl TxEnvelope {
         match self {
             Self::Native(inner) => inner.eip2718_encoded_length(),
             Self::Eip712(inner) => inner.tx().encoded_length(inner.signature()),
+            Self::L1Priority(inner) | Self::ProtocolUpgrade(inner) => {
+                1 + inner.encoded_length(inner.hash_ref())
+            }
         }
     }
 }
@@ -189,6 +250,7 @@ impl Typed2718 for TxEnvelope {
         match self {
             Self::Native(inner) => inner.ty(),
             Self::Eip712(inner) => inner.tx().tx_type() as u8,
+            Self::L1Priority(inner) | Self::ProtocolUpgrade(inner) => inner.ty(),
         }
     }
 }
@@ -198,6 +260,7 @@ impl Encodable2718 for TxEnvelope {
         match self {
             Self::Native(inner) => inner.type_flag(),
             Self::Eip712(inner) => Some(inner.tx().tx_type() as u8),
+            Self::L1Priority(inner) | Self::ProtocolUpgrade(inner) => Some(inner.ty()),
         }
     }
 
@@ -215,6 +278,9 @@ impl Encodable2718 for TxEnvelope {
                 .length()
                     + payload_length
             }
+            Self::L1Priority(inner) | Self::ProtocolUpgrade(inner) => {
+                inner.encoded_length(inner.hash_ref())
+            }
         }
     }
 
@@ -224,17 +290,37 @@ impl Encodable2718 for TxEnvelope {
             Self::Eip712(tx) => {
                 tx.tx().encode_with_signature(tx.signature(), out);
             }
+            Self::L1Priority(tx) | Self::ProtocolUpgrade(tx) => {
+                tx.encode_sealed(tx.hash_ref(), out);
+            }
         }
     }
 }
 
 impl Decodable2718 for TxEnvelope {
+    fn extract_type_byte(buf: &mut &[u8]) -> Option<u8> {
+        // L1-originated transaction types are above the EIP-2718 range, so they would otherwise
+        // be decoded as legacy transactions. Legacy transactions can't start with these bytes,
+        // since that would require an RLP list longer than 2^48 bytes.
+        buf.first().copied().filter(|ty| {
+            *ty <= 0x7f || *ty == TxType::ProtocolUpgrade as u8 || *ty == TxType::L1Priority as u8
+        })
+    }
+
     fn typed_decode(ty: u8, buf: &mut &[u8]) -> alloy::network::eip2718::Eip2718Result<Self> {
         match ty {
             _ if ty == (TxType::Eip712 as u8) => {
                 let tx = TxEip712::decode_signed_fields(buf)?;
                 Ok(Self::Eip712(tx))
             }
+            _ if ty == (TxType::L1Priority as u8) => {
+                let tx = TxL1::decode_sealed(TxType::L1Priority, buf)?;
+                Ok(Self::L1Priority(tx))
+            }
+            _ if ty == (TxType::ProtocolUpgrade as u8) => {
+                let tx = TxL1::decode_sealed(TxType::ProtocolUpgrade, buf)?;
+                Ok(Self::ProtocolUpgrade(tx))
+            }
             _ => {
                 let inner = alloy::consensus::TxEnvelope::typed_decode(ty, buf)?;
                 Ok(Self::Native(inner))
@@ -253,6 +339,9 @@ impl AsRef<dyn alloy::consensus::Transaction> for TxEnvelope {
         match self {
             TxEnvelope::Native(inner) => inner,
             TxEnvelope::Eip712(signed_inner) => signed_inner.tx(),
+            TxEnvelope::L1Priority(sealed_inner) | TxEnvelope::ProtocolUpgrade(sealed_inner) => {
+                sealed_inner.inner()
+            }
         }
     }
 }
@@ -330,8 +419,8 @@ impl alloy::consensus::Transaction for TxEnvelope {
 mod serde_from {
     //! NB: Why do we need this?
     //!
-    //! We are following the same approach as [`alloy::consensus::TxEnvelope`] but with an additional
-    //! ZKsync-specific transaction type (`type: "0x71"`).
+    //! We are following the same approach as [`alloy::consensus::TxEnvelope`] but with additional
+    //! ZKsync-specific transaction types (`type: "0x71"`, `type: "0xff"`, and `type: "0xfe"`).
     //!
     //! Because the tag may be missing, we need an abstraction over tagged (with
     //! type) and untagged (always legacy). This is [`MaybeTaggedTxEnvelope`].
@@ -342,7 +431,9 @@ mod serde_from {
     //! [`MaybeTaggedTxEnvelope`].
     use crate::network::tx_envelope::TxEnvelope;
     use crate::network::unsigned_tx::eip712::TxEip712;
+    use crate::network::unsigned_tx::l1::{TxL1, sealed_serde};
     use alloy::consensus::{Signed, TxEip1559, TxEip2930, TxEip4844Variant, TxEip7702, TxLegacy};
+    use alloy::primitives::Sealed;
 
     #[derive(Debug, serde::Deserialize)]
     #[serde(untagged)]
@@ -378,9 +469,21 @@ mod serde_from {
         Eip4844(Signed<TxEip4844Variant>),
         #[serde(rename = "0x4", alias = "0x04")]
         Eip7702(Signed<TxEip7702>),
-        // Custom ZKsync transaction type
+        // Custom ZKsync transaction types
         #[serde(rename = "0x71")]
         Eip712(Signed<TxEip712>),
+        #[serde(
+            rename = "0xff",
+            serialize_with = "sealed_serde::serialize",
+            deserialize_with = "sealed_serde::deserialize_l1_priority"
+        )]
+        L1Priority(Sealed<TxL1>),
+        #[serde(
+            rename = "0xfe",
+            serialize_with = "sealed_serde::serialize",
+            deserialize_with = "sealed_serde::deserialize_protocol_upgrade"
+        )]
+        ProtocolUpgrade(Sealed<TxL1>),
     }
 
     impl From<MaybeTaggedTxEnvelope> for TxEnvelope {
@@ -413,6 +516,8 @@ mod serde_from {
                     Self::Native(alloy::consensus::TxEnvelope::Eip7702(signed))
                 }
                 TaggedTxEnvelope::Eip712(signed) => Self::Eip712(signed),
+                TaggedTxEnvelope::L1Priority(sealed) => Self::L1Priority(sealed),
+                TaggedTxEnvelope::ProtocolUpgrade(sealed) => Self::ProtocolUpgrade(sealed),
             }
         }
     }
@@ -436,6 +541,8 @@ mod serde_from {
                     Self::Eip7702(signed)
                 }
                 TxEnvelope::Eip712(signed) => Self::Eip712(signed),
+                TxEnvelope::L1Priority(sealed) => Self::L1Priority(sealed),
+                TxEnvelope::ProtocolUpgrade(sealed) => Self::ProtocolUpgrade(sealed),
             }
         }
     }
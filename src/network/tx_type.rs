@@ -18,7 +18,10 @@ pub enum TxType {
     Eip7702 = 4,
     /// ZKsync-specific EIP712-based transaction type.
     Eip712 = 0x71,
-    // TODO: L1-based transaction type
+    /// ZKsync-specific protocol upgrade transaction type.
+    ProtocolUpgrade = 0xfe,
+    /// ZKsync-specific L1 priority operation transaction type.
+    L1Priority = 0xff,
 }
 
 impl TxType {
@@ -31,7 +34,7 @@ impl TxType {
             TxType::Eip1559 => alloy::consensus::TxType::Eip1559,
             TxType::Eip4844 => alloy::consensus::TxType::Eip4844,
             TxType::Eip7702 => alloy::consensus::TxType::Eip7702,
-            TxType::Eip712 => return None,
+            TxType::Eip712 | TxType::ProtocolUpgrade | TxType::L1Priority => return None,
         })
     }
 }
@@ -64,6 +67,8 @@ impl fmt::Display for TxType {
             Self::Eip4844 => write!(f, "EIP-4844"),
             Self::Eip7702 => write!(f, "EIP-7702"),
             Self::Eip712 => write!(f, "Era EIP-712"),
+            Self::ProtocolUpgrade => write!(f, "Era protocol upgrade"),
+            Self::L1Priority => write!(f, "Era L1 priority"),
         }
     }
 }
@@ -79,6 +84,8 @@ impl TryFrom<u8> for TxType {
             3 => Self::Eip4844,
             4 => Self::Eip7702,
             0x71 => Self::Eip712,
+            0xfe => Self::ProtocolUpgrade,
+            0xff => Self::L1Priority,
             _ => return Err(Eip2718Error::UnexpectedType(value)),
         })
     }
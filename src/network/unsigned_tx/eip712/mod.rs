@@ -4,7 +4,7 @@ use alloy::consensus::{
     SignableTransaction, Signed, Transaction, Typed2718, transaction::RlpEcdsaEncodableTx,
 };
 use alloy::primitives::Signature;
-use alloy::primitives::{Address, Bytes, ChainId, TxKind, U256, keccak256};
+use alloy::primitives::{Address, B256, Bytes, ChainId, TxKind, U256, keccak256};
 use alloy::rlp::{BufMut, Decodable, Encodable, Header};
 use alloy::rpc::types::TransactionInput;
 use serde::{Deserialize, Serialize};
@@ -12,9 +12,15 @@ use serde::{Deserialize, Serialize};
 use crate::network::tx_type::TxType;
 
 pub use self::meta::{Eip712Meta, PaymasterParams};
-pub use self::utils::{BytecodeHashError, hash_bytecode};
+pub use self::paymaster::{IPaymasterFlow, PaymasterFlow};
+pub use self::utils::{
+    BytecodeCompressionError, BytecodeHashError, compress_bytecode, compute_create_address,
+    compute_create2_address, estimate_factory_deps_pubdata, hash_bytecode, hash_evm_bytecode,
+    verify_compressed_bytecode,
+};
 
 mod meta;
+mod paymaster;
 mod signing;
 mod utils;
 
@@ -107,6 +113,32 @@ impl TxEip712 {
         }
     }
 
+    /// Returns the EIP-712 hash of the transaction data, i.e. the hash that has to be signed.
+    pub fn signature_hash(&self) -> B256 {
+        keccak256(self.encoded_for_signing())
+    }
+
+    /// Computes the ZKsync transaction hash for the provided signature.
+    ///
+    /// The hash commits both to the signed data and to the signature. If the transaction has
+    /// a non-empty custom signature, it is used instead of the provided one, same as on the node.
+    pub fn tx_hash(&self, signature: &Signature) -> B256 {
+        let custom_signature = self
+            .eip712_meta
+            .as_ref()
+            .and_then(|meta| meta.custom_signature.as_ref())
+            .filter(|custom_signature| !custom_signature.is_empty());
+        let signature_hash = match custom_signature {
+            Some(custom_signature) => keccak256(custom_signature),
+            None => keccak256(signature.as_bytes()),
+        };
+
+        let mut buf = [0u8; 64];
+        buf[..32].copy_from_slice(self.signature_hash().as_slice());
+        buf[32..].copy_from_slice(signature_hash.as_slice());
+        keccak256(buf)
+    }
+
     /// Inner encoding function that is used for both rlp [`Encodable`] trait and for calculating
     /// hash that for eip2718 does not require a rlp header.
     pub(crate) fn encode_with_signature(
@@ -358,11 +390,7 @@ impl SignableTransaction<Signature> for TxEip712 {
     }
 
     fn into_signed(self, signature: Signature) -> Signed<Self> {
-        let mut buf = [0u8; 64];
-        buf[..32].copy_from_slice(self.signature_hash().as_slice());
-        buf[32..].copy_from_slice(keccak256(signature.as_bytes()).as_slice());
-        let hash = keccak256(buf);
-
+        let hash = self.tx_hash(&signature);
         Signed::new_unchecked(self, signature, hash)
     }
 }
@@ -447,9 +475,12 @@ mod tests {
     use crate::network::unsigned_tx::eip712::{Eip712Meta, PaymasterParams};
 
     use super::TxEip712;
+    use crate::network::tx_envelope::TxEnvelope;
     use alloy::consensus::SignableTransaction;
     use alloy::hex::FromHex;
-    use alloy::primitives::{Address, B256, Bytes, FixedBytes, Signature, U256, address, hex};
+    use alloy::primitives::{
+        Address, B256, Bytes, FixedBytes, Signature, U256, address, hex, keccak256,
+    };
 
     #[test]
     fn decode_eip712_tx() {
@@ -599,44 +630,66 @@ mod tests {
         assert_eq!(decoded, tx.into_signed(signature));
     }
 
-    // #[test]
-    // fn recover_signer_eip712() {
-    //     let signer: Address = address!("dd6b8b3dc6b7ad97db52f08a275ff4483e024cea");
-    //     let hash: B256 = b256!("0ec0b6a2df4d87424e5f6ad2a654e27aaeb7dac20ae9e8385cc09087ad532ee0");
+    #[test]
+    fn recover_signer_eip712() {
+        let tx = TxEip712 {
+            chain_id: 270,
+            from: Address::from_str("0xe30f4fb40666753a7596d315f2f1f1d140d1508b").unwrap(),
+            to: Address::from_str("0x82112600a140ceaa9d7da373bb65453f7d99af4b").unwrap(),
+            nonce: U256::from(1),
+            value: U256::from(10),
+            gas: 12,
+            max_fee_per_gas: 11,
+            max_priority_fee_per_gas: 0,
+            input: vec![0x01, 0x02, 0x03].into(),
+            eip712_meta: Some(Eip712Meta {
+                gas_per_pubdata: U256::from(4),
+                factory_deps: vec![vec![2; 32].into()],
+                custom_signature: Some(vec![].into()),
+                paymaster_params: None,
+            }),
+        };
+        let signature = Signature::from_str("0x3faf83b5451ad3001f96f577b0bb5dfcaa7769ab11908f281dc6b15c45a3986f0325197832aac9a7ab2f5a83873834d457e0d22c1e72377d45364c6968f8ac3b1c").unwrap();
 
-    //     let tx =  TxEip712 {
-    //         chain_id: 1,
-    //         nonce: 0x42,
-    //         gas_limit: 44386,
-    //         to: address!("6069a6c32cf691f5982febae4faf8a6f3ab2f0f6").into(),
-    //         value: U256::from(0_u64),
-    //         input:  hex!("a22cb4650000000000000000000000005eee75727d804a2b13038928d36f8b188945a57a0000000000000000000000000000000000000000000000000000000000000000").into(),
-    //         max_fee_per_gas: 0x4a817c800,
-    //         max_priority_fee_per_gas: 0x3b9aca00,
-    //         access_list: AccessList::default(),
-    //         eip712_meta: Eip712Meta::default(),
-    //     };
+        let signed_tx = tx.clone().into_signed(signature);
+        assert_eq!(signed_tx.recover_signer().unwrap(), tx.from);
 
-    //     let sig = Signature::from_scalars_and_parity(
-    //         b256!("840cfc572845f5786e702984c2a582528cad4b49b2a10b9db1be7fca90058565"),
-    //         b256!("25e7109ceb98168d95b09b18bbf6b685130e0562f233877d492b94eee0c5b6d1"),
-    //         false,
-    //     )
-    //     .unwrap();
+        let envelope = TxEnvelope::Eip712(signed_tx);
+        assert_eq!(envelope.recover_signer().unwrap(), tx.from);
+    }
 
-    //     assert_eq!(
-    //         tx.signature_hash(),
-    //         hex!("0d5688ac3897124635b6cf1bc0e29d6dfebceebdc10a54d74f2ef8b56535b682")
-    //     );
-
-    //     let signed_tx = tx.into_signed(sig);
-    //     assert_eq!(*signed_tx.hash(), hash, "Expected same hash");
-    //     // assert_eq!(
-    //     //     signed_tx.recover_signer().unwrap(),
-    //     //     signer,
-    //     //     "Recovering signer should pass."
-    //     // );
-    // }
+    #[test]
+    fn tx_hash_uses_custom_signature() {
+        let custom_signature = Bytes::from_hex("0x010203").unwrap();
+        let tx = TxEip712 {
+            chain_id: 270,
+            from: Address::from_str("0xe30f4fb40666753a7596d315f2f1f1d140d1508b").unwrap(),
+            to: Address::from_str("0x82112600a140ceaa9d7da373bb65453f7d99af4b").unwrap(),
+            nonce: U256::from(1),
+            value: U256::from(10),
+            gas: 12,
+            max_fee_per_gas: 11,
+            max_priority_fee_per_gas: 0,
+            input: vec![0x01, 0x02, 0x03].into(),
+            eip712_meta: Some(Eip712Meta {
+                gas_per_pubdata: U256::from(4),
+                factory_deps: vec![],
+                custom_signature: Some(custom_signature.clone()),
+                paymaster_params: None,
+            }),
+        };
+        let signature = Signature::from_str("0x3faf83b5451ad3001f96f577b0bb5dfcaa7769ab11908f281dc6b15c45a3986f0325197832aac9a7ab2f5a83873834d457e0d22c1e72377d45364c6968f8ac3b1c").unwrap();
+
+        let expected_hash = keccak256(
+            [
+                tx.signature_hash().as_slice(),
+                keccak256(&custom_signature).as_slice(),
+            ]
+            .concat(),
+        );
+        assert_eq!(tx.tx_hash(&signature), expected_hash);
+        assert_eq!(*tx.into_signed(signature).hash(), expected_hash);
+    }
 
     // #[test]
     // fn encode_decode_eip712() {
@@ -0,0 +1,168 @@
+//! Typed encoding of the paymaster input for the standard paymaster flows.
+
+use alloy::{
+    primitives::{Address, Bytes, U256},
+    sol_types::{SolCall, SolInterface},
+};
+
+use super::PaymasterParams;
+
+alloy::sol! {
+    /// Interface describing the standard paymaster flows.
+    /// The paymaster input must start with the selector of one of these functions.
+    interface IPaymasterFlow {
+        /// Flow in which the paymaster pays for the transaction without any conditions.
+        function general(bytes calldata input);
+
+        /// Flow in which the user approves `_token` to the paymaster before the transaction is executed.
+        function approvalBased(address _token, uint256 _minAllowance, bytes calldata _innerInput);
+    }
+}
+
+/// Paymaster flow decoded from the paymaster input.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum PaymasterFlow {
+    /// General paymaster flow.
+    General {
+        /// Input passed to the paymaster.
+        inner_input: Bytes,
+    },
+    /// Approval-based paymaster flow.
+    ApprovalBased {
+        /// Token approved to the paymaster.
+        token: Address,
+        /// Minimal allowance of the token the paymaster expects.
+        min_allowance: U256,
+        /// Input passed to the paymaster.
+        inner_input: Bytes,
+    },
+}
+
+impl PaymasterFlow {
+    /// Encodes the flow into the paymaster input.
+    pub fn encode(&self) -> Bytes {
+        match self {
+            Self::General { inner_input } => IPaymasterFlow::generalCall {
+                input: inner_input.clone(),
+            }
+            .abi_encode()
+            .into(),
+            Self::ApprovalBased {
+                token,
+                min_allowance,
+                inner_input,
+            } => IPaymasterFlow::approvalBasedCall {
+                _token: *token,
+                _minAllowance: *min_allowance,
+                _innerInput: inner_input.clone(),
+            }
+            .abi_encode()
+            .into(),
+        }
+    }
+
+    /// Decodes the flow from the paymaster input.
+    ///
+    /// Returns an error if the input does not correspond to any of the standard flows.
+    pub fn decode(paymaster_input: &[u8]) -> Result<Self, alloy::sol_types::Error> {
+        Ok(
+            match IPaymasterFlow::IPaymasterFlowCalls::abi_decode(paymaster_input)? {
+                IPaymasterFlow::IPaymasterFlowCalls::general(call) => Self::General {
+                    inner_input: call.input,
+                },
+                IPaymasterFlow::IPaymasterFlowCalls::approvalBased(call) => Self::ApprovalBased {
+                    token: call._token,
+                    min_allowance: call._minAllowance,
+                    inner_input: call._innerInput,
+                },
+            },
+        )
+    }
+}
+
+impl PaymasterParams {
+    /// Creates paymaster parameters for the provided paymaster flow.
+    pub fn new(paymaster: Address, flow: &PaymasterFlow) -> Self {
+        Self {
+            paymaster,
+            paymaster_input: flow.encode(),
+        }
+    }
+
+    /// Creates paymaster parameters for the general paymaster flow.
+    pub fn general(paymaster: Address, inner_input: Bytes) -> Self {
+        Self::new(paymaster, &PaymasterFlow::General { inner_input })
+    }
+
+    /// Creates paymaster parameters for the approval-based paymaster flow.
+    pub fn approval_based(
+        paymaster: Address,
+        token: Address,
+        min_allowance: U256,
+        inner_input: Bytes,
+    ) -> Self {
+        Self::new(
+            paymaster,
+            &PaymasterFlow::ApprovalBased {
+                token,
+                min_allowance,
+                inner_input,
+            },
+        )
+    }
+
+    /// Decodes the paymaster flow from the paymaster input.
+    ///
+    /// Returns an error if the input does not correspond to any of the standard flows.
+    pub fn flow(&self) -> Result<PaymasterFlow, alloy::sol_types::Error> {
+        PaymasterFlow::decode(&self.paymaster_input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::{address, bytes};
+
+    #[test]
+    fn general_flow_roundtrip() {
+        let paymaster = address!("99E12239CBf8112fBB3f7Fd473d0558031abcbb5");
+        let params = PaymasterParams::general(paymaster, Bytes::new());
+
+        assert_eq!(params.paymaster, paymaster);
+        assert_eq!(
+            params.paymaster_input,
+            bytes!(
+                "8c5a344500000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000000000000000000000000000000000000"
+            )
+        );
+        assert_eq!(
+            params.flow().unwrap(),
+            PaymasterFlow::General {
+                inner_input: Bytes::new()
+            }
+        );
+    }
+
+    #[test]
+    fn approval_based_flow_roundtrip() {
+        let paymaster = address!("99E12239CBf8112fBB3f7Fd473d0558031abcbb5");
+        let token = address!("927488F48ffbc32112F1fF721759649A89721F8F");
+        let params = PaymasterParams::approval_based(paymaster, token, U256::from(1), Bytes::new());
+
+        assert_eq!(&params.paymaster_input[..4], &[0x94, 0x94, 0x31, 0xdc]);
+        assert_eq!(
+            params.flow().unwrap(),
+            PaymasterFlow::ApprovalBased {
+                token,
+                min_allowance: U256::from(1),
+                inner_input: Bytes::new(),
+            }
+        );
+    }
+
+    #[test]
+    fn unknown_flow_is_rejected() {
+        assert!(PaymasterFlow::decode(&[0xde, 0xad, 0xbe, 0xef]).is_err());
+    }
+}
@@ -1,8 +1,15 @@
+use std::collections::{HashMap, HashSet};
+
+use alloy::primitives::{Address, B256, U256, keccak256};
 use k256::sha2::{self, Digest};
 
 // Bytecode length in words must fit in u16.
 const WORD_SIZE: usize = 32;
 const MAX_BYTECODE_LENGTH: usize = WORD_SIZE * u16::MAX as usize;
+// Bytecode is compressed in 8-byte chunks.
+const COMPRESSION_CHUNK_SIZE: usize = 8;
+// Size of a serialized L2 -> L1 log, which is published for every L2 -> L1 message.
+const L2_TO_L1_LOG_SERIALIZE_SIZE: usize = 88;
 
 /// Errors that can occur during bytecode hashing.
 #[derive(Debug, thiserror::Error)]
@@ -15,6 +22,11 @@ pub enum BytecodeHashError {
     BytecodeLengthExceedsLimit { num_words: usize },
     #[error("Bytecode must have odd number of words")]
     NumberOfWordsMustBeOdd,
+    #[error(
+        "EVM bytecode length exceeds limit: {length} bytes, the maximum is {}",
+        u16::MAX
+    )]
+    EvmBytecodeLengthExceedsLimit { length: usize },
 }
 
 /// The 32-byte hash of the bytecode of a zkSync contract is calculated in the following way:
@@ -28,7 +40,20 @@ pub enum BytecodeHashError {
 /// - The bytecode length must not exceed the maximum allowed value.
 /// - The number of words must be odd.
 pub fn hash_bytecode(bytecode: &[u8]) -> Result<[u8; 32], BytecodeHashError> {
-    if bytecode.len() % WORD_SIZE != 0 {
+    let bytecode_length = validate_bytecode(bytecode)?;
+    let bytecode_hash: [u8; 32] = sha2::Sha256::digest(bytecode).into();
+
+    let mut contract_hash: [u8; 32] = [0u8; 32];
+    contract_hash[..2].copy_from_slice(&0x0100_u16.to_be_bytes());
+    contract_hash[2..4].copy_from_slice(&bytecode_length.to_be_bytes());
+    contract_hash[4..].copy_from_slice(&bytecode_hash[4..]);
+
+    Ok(contract_hash)
+}
+
+/// Checks that the EraVM bytecode is valid and returns its length in words.
+fn validate_bytecode(bytecode: &[u8]) -> Result<u16, BytecodeHashError> {
+    if !bytecode.len().is_multiple_of(WORD_SIZE) {
         return Err(BytecodeHashError::BytecodeNotAligned);
     }
 
@@ -41,20 +66,185 @@ pub fn hash_bytecode(bytecode: &[u8]) -> Result<[u8; 32], BytecodeHashError> {
     if bytecode_length % 2 == 0 {
         return Err(BytecodeHashError::NumberOfWordsMustBeOdd);
     }
+    Ok(bytecode_length)
+}
 
-    let bytecode_hash: [u8; 32] = sha2::Sha256::digest(bytecode).into();
+/// Computes the versioned hash of the deployed EVM bytecode, as used by ZKsync chains with
+/// the EVM emulator enabled:
+///
+/// * The first 2 bytes denote the version of bytecode hash format and are equal to `[2,0]`.
+/// * The second 2 bytes denote the length of the unpadded bytecode in bytes.
+/// * The rest of the 28-byte are equal to the last 28 bytes of the sha256 hash of the bytecode
+///   padded to an odd number of 32-byte words.
+pub fn hash_evm_bytecode(bytecode: &[u8]) -> Result<[u8; 32], BytecodeHashError> {
+    let bytecode_length = u16::try_from(bytecode.len()).map_err(|_| {
+        BytecodeHashError::EvmBytecodeLengthExceedsLimit {
+            length: bytecode.len(),
+        }
+    })?;
+
+    let mut num_words = bytecode.len().div_ceil(WORD_SIZE);
+    if num_words.is_multiple_of(2) {
+        num_words += 1;
+    }
+    let mut padded_bytecode = bytecode.to_vec();
+    padded_bytecode.resize(num_words * WORD_SIZE, 0);
+    let bytecode_hash: [u8; 32] = sha2::Sha256::digest(&padded_bytecode).into();
 
     let mut contract_hash: [u8; 32] = [0u8; 32];
-    contract_hash[..2].copy_from_slice(&0x0100_u16.to_be_bytes());
+    contract_hash[..2].copy_from_slice(&0x0200_u16.to_be_bytes());
     contract_hash[2..4].copy_from_slice(&bytecode_length.to_be_bytes());
     contract_hash[4..].copy_from_slice(&bytecode_hash[4..]);
 
     Ok(contract_hash)
 }
 
+/// Errors that can occur during bytecode compression.
+#[derive(Debug, thiserror::Error)]
+pub enum BytecodeCompressionError {
+    #[error("Invalid bytecode: {0}")]
+    InvalidBytecode(#[from] BytecodeHashError),
+    #[error("Bytecode has more than {} unique 8-byte chunks", u16::MAX)]
+    DictionaryOverflow,
+    #[error("Compressed bytecode is malformed")]
+    MalformedCompressedBytecode,
+    #[error("Compressed bytecode doesn't match the original one at chunk {0}")]
+    ChunkMismatch(usize),
+}
+
+/// Compresses the EraVM bytecode the same way as expected by the `Compressor` system contract,
+/// which publishes the bytecodes to L1.
+///
+/// The bytecode is split into 8-byte chunks. The compressed bytecode consists of:
+///
+/// * The number of unique chunks as 2 big-endian bytes.
+/// * The dictionary of unique chunks, 8 bytes each, ordered from the most to the least frequent one.
+/// * The encoded data: for each chunk of the original bytecode, its index in the dictionary as 2
+///   big-endian bytes.
+pub fn compress_bytecode(bytecode: &[u8]) -> Result<Vec<u8>, BytecodeCompressionError> {
+    validate_bytecode(bytecode)?;
+
+    // Number of occurrences and the position of the first occurrence for each chunk.
+    let mut statistic: HashMap<u64, (usize, usize)> = HashMap::new();
+    for (position, chunk) in bytecode.chunks(COMPRESSION_CHUNK_SIZE).enumerate() {
+        let chunk = u64::from_be_bytes(chunk.try_into().unwrap());
+        statistic.entry(chunk).or_insert((0, position)).0 += 1;
+    }
+    if statistic.len() > u16::MAX as usize {
+        return Err(BytecodeCompressionError::DictionaryOverflow);
+    }
+
+    // The most popular chunks get the smallest indexes, so that the encoded data contains
+    // more zero bytes, which are cheaper to publish.
+    let mut statistic: Vec<_> = statistic.into_iter().collect();
+    statistic.sort_by_key(|&(_, stats)| std::cmp::Reverse(stats));
+    let dictionary: HashMap<u64, u16> = statistic
+        .iter()
+        .enumerate()
+        .map(|(index, &(chunk, _))| (chunk, index as u16))
+        .collect();
+
+    let mut compressed =
+        Vec::with_capacity(2 + statistic.len() * COMPRESSION_CHUNK_SIZE + bytecode.len() / 4);
+    compressed.extend((statistic.len() as u16).to_be_bytes());
+    for (chunk, _) in &statistic {
+        compressed.extend(chunk.to_be_bytes());
+    }
+    for chunk in bytecode.chunks(COMPRESSION_CHUNK_SIZE) {
+        let chunk = u64::from_be_bytes(chunk.try_into().unwrap());
+        compressed.extend(dictionary[&chunk].to_be_bytes());
+    }
+    Ok(compressed)
+}
+
+/// Verifies that the compressed bytecode corresponds to the original one, performing the same
+/// checks as the `Compressor` system contract.
+pub fn verify_compressed_bytecode(
+    bytecode: &[u8],
+    compressed: &[u8],
+) -> Result<(), BytecodeCompressionError> {
+    validate_bytecode(bytecode)?;
+
+    let (dictionary_length, rest) = compressed
+        .split_first_chunk::<2>()
+        .ok_or(BytecodeCompressionError::MalformedCompressedBytecode)?;
+    let dictionary_length = u16::from_be_bytes(*dictionary_length) as usize;
+    let (dictionary, encoded_data) = rest
+        .split_at_checked(dictionary_length * COMPRESSION_CHUNK_SIZE)
+        .ok_or(BytecodeCompressionError::MalformedCompressedBytecode)?;
+    if encoded_data.len() * 4 != bytecode.len() {
+        return Err(BytecodeCompressionError::MalformedCompressedBytecode);
+    }
+
+    let chunks = bytecode.chunks(COMPRESSION_CHUNK_SIZE);
+    for (position, (index, chunk)) in encoded_data.chunks(2).zip(chunks).enumerate() {
+        let index = u16::from_be_bytes([index[0], index[1]]) as usize;
+        let start = index * COMPRESSION_CHUNK_SIZE;
+        let dictionary_chunk = dictionary
+            .get(start..start + COMPRESSION_CHUNK_SIZE)
+            .ok_or(BytecodeCompressionError::MalformedCompressedBytecode)?;
+        if dictionary_chunk != chunk {
+            return Err(BytecodeCompressionError::ChunkMismatch(position));
+        }
+    }
+    Ok(())
+}
+
+/// Estimates the amount of pubdata required to publish the factory dependencies to L1.
+///
+/// Bytecodes that can be compressed are published via the `Compressor` system contract as an
+/// L2 -> L1 message, others are published as is. Duplicates are only counted once. Bytecodes
+/// already known to the network are not published, so they should be excluded by the caller.
+///
+/// The cost of publishing in gas is the returned value multiplied by the gas per pubdata byte.
+pub fn estimate_factory_deps_pubdata<T: AsRef<[u8]>>(factory_deps: &[T]) -> usize {
+    let mut seen = HashSet::new();
+    factory_deps
+        .iter()
+        .map(AsRef::as_ref)
+        .filter(|bytecode| seen.insert(*bytecode))
+        .map(|bytecode| match compress_bytecode(bytecode) {
+            Ok(compressed) => L2_TO_L1_LOG_SERIALIZE_SIZE + 4 + compressed.len(),
+            Err(_) => 4 + bytecode.len(),
+        })
+        .sum()
+}
+
+/// Computes the address of a contract deployed via `create` on ZKsync.
+///
+/// Unlike Ethereum, ZKsync derives the address from the deployment nonce of the sender,
+/// which is tracked separately from the transaction nonce by the `NonceHolder` system contract.
+pub fn compute_create_address(sender: Address, deployment_nonce: U256) -> Address {
+    let mut preimage = [0u8; 96];
+    preimage[..32].copy_from_slice(keccak256("zksyncCreate").as_slice());
+    preimage[44..64].copy_from_slice(sender.as_slice());
+    preimage[64..].copy_from_slice(&deployment_nonce.to_be_bytes::<32>());
+    Address::from_word(keccak256(preimage))
+}
+
+/// Computes the address of a contract deployed via `create2` on ZKsync.
+///
+/// Unlike Ethereum, ZKsync uses the bytecode hash as returned by [`hash_bytecode`] and
+/// also commits to the constructor input.
+pub fn compute_create2_address(
+    sender: Address,
+    bytecode_hash: B256,
+    salt: B256,
+    constructor_input: &[u8],
+) -> Address {
+    let mut preimage = [0u8; 160];
+    preimage[..32].copy_from_slice(keccak256("zksyncCreate2").as_slice());
+    preimage[44..64].copy_from_slice(sender.as_slice());
+    preimage[64..96].copy_from_slice(salt.as_slice());
+    preimage[96..128].copy_from_slice(bytecode_hash.as_slice());
+    preimage[128..].copy_from_slice(keccak256(constructor_input).as_slice());
+    Address::from_word(keccak256(preimage))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloy::primitives::address;
     use assert_matches::assert_matches;
 
     #[test]
@@ -80,4 +270,116 @@ mod tests {
             Err(BytecodeHashError::BytecodeNotAligned)
         );
     }
+
+    #[test]
+    fn create_address() {
+        // Reference value calculated using zksync-ethers.
+        let sender = address!("36615Cf349d7F6344891B1e7CA7C72883F5dc049");
+        assert_eq!(
+            compute_create_address(sender, U256::from(1)),
+            address!("4B5DF730c2e6b28E17013A1485E5d9BC41Efe021")
+        );
+        assert_ne!(
+            compute_create_address(sender, U256::from(2)),
+            compute_create_address(sender, U256::from(1))
+        );
+    }
+
+    #[test]
+    fn create2_address() {
+        let sender = address!("36615Cf349d7F6344891B1e7CA7C72883F5dc049");
+        let bytecode_hash = B256::from(hash_bytecode(&[10u8; 32]).unwrap());
+        let salt = B256::repeat_byte(1);
+
+        // Reference values calculated as in `create2Address` of zksync-ethers.
+        let address = compute_create2_address(sender, bytecode_hash, salt, &[]);
+        assert_eq!(
+            address,
+            address!("f03a0d4b19b0756a3cdf67afb7b6974aa5850f06")
+        );
+        assert_eq!(
+            compute_create2_address(sender, bytecode_hash, salt, &[1, 2, 3]),
+            address!("30b78f6e86353df67639a9c546326721d19b74f6")
+        );
+        assert_ne!(
+            address,
+            sender.create2(salt, bytecode_hash),
+            "must not match the Ethereum derivation"
+        );
+    }
+
+    #[test]
+    fn evm_bytecode_hash() {
+        let bytecode = [0x60, 0x80, 0x60, 0x40, 0x52];
+        let hash = hash_evm_bytecode(&bytecode).unwrap();
+        assert_eq!(&hash[..4], &[2, 0, 0, 5]);
+
+        // The bytecode is padded to a single word.
+        let mut padded = bytecode.to_vec();
+        padded.resize(32, 0);
+        assert_eq!(&hash[4..], &sha2::Sha256::digest(&padded)[4..]);
+
+        // 33 bytes are padded to 3 words.
+        let hash = hash_evm_bytecode(&[1; 33]).unwrap();
+        let mut padded = vec![1; 33];
+        padded.resize(96, 0);
+        assert_eq!(&hash[..4], &[2, 0, 0, 33]);
+        assert_eq!(&hash[4..], &sha2::Sha256::digest(&padded)[4..]);
+
+        assert_matches!(
+            hash_evm_bytecode(&vec![0; u16::MAX as usize + 1]),
+            Err(BytecodeHashError::EvmBytecodeLengthExceedsLimit { .. })
+        );
+    }
+
+    #[test]
+    fn bytecode_compression() {
+        let bytecode = [[0x01; 8], [0x02; 8], [0x01; 8], [0x01; 8]]
+            .repeat(8)
+            .concat();
+        let bytecode = [bytecode, vec![0x02; 32]].concat();
+        let compressed = compress_bytecode(&bytecode).unwrap();
+
+        // Two unique chunks, the most frequent one goes first.
+        assert_eq!(&compressed[..2], &[0, 2]);
+        assert_eq!(&compressed[2..10], &[0x01; 8]);
+        assert_eq!(&compressed[10..18], &[0x02; 8]);
+        assert_eq!(&compressed[18..26], &[0, 0, 0, 1, 0, 0, 0, 0]);
+        assert_eq!(compressed.len(), 2 + 2 * 8 + bytecode.len() / 4);
+        verify_compressed_bytecode(&bytecode, &compressed).unwrap();
+
+        let mut tampered = compressed.clone();
+        tampered[19] = 1;
+        assert_matches!(
+            verify_compressed_bytecode(&bytecode, &tampered),
+            Err(BytecodeCompressionError::ChunkMismatch(0))
+        );
+        let mut tampered = compressed.clone();
+        tampered[19] = 2;
+        assert_matches!(
+            verify_compressed_bytecode(&bytecode, &tampered),
+            Err(BytecodeCompressionError::MalformedCompressedBytecode)
+        );
+        assert_matches!(
+            verify_compressed_bytecode(&bytecode, &compressed[..compressed.len() - 2]),
+            Err(BytecodeCompressionError::MalformedCompressedBytecode)
+        );
+        assert_matches!(
+            compress_bytecode(&[1; 64]),
+            Err(BytecodeCompressionError::InvalidBytecode(
+                BytecodeHashError::NumberOfWordsMustBeOdd
+            ))
+        );
+    }
+
+    #[test]
+    fn factory_deps_pubdata() {
+        let bytecode = vec![0x01; 96];
+        let compressed_length = compress_bytecode(&bytecode).unwrap().len();
+        assert_eq!(
+            estimate_factory_deps_pubdata(&[bytecode.clone(), bytecode.clone()]),
+            L2_TO_L1_LOG_SERIALIZE_SIZE + 4 + compressed_length
+        );
+        assert_eq!(estimate_factory_deps_pubdata::<Vec<u8>>(&[]), 0);
+    }
 }
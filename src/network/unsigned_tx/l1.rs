@@ -0,0 +1,464 @@
+//! L1-originated transaction types, specific to the ZKsync network.
+
+use alloy::consensus::{Transaction, Typed2718};
+use alloy::primitives::{Address, B256, Bytes, ChainId, Sealed, TxKind, U256};
+use alloy::rlp::{BufMut, Decodable, Encodable, Header};
+use alloy::rpc::types::TransactionInput;
+use serde::{Deserialize, Serialize};
+
+use crate::network::tx_type::TxType;
+use crate::types::{ExecuteTransactionCommon, Transaction as RawTransaction};
+
+/// Transaction initiated on L1: either a priority operation or a protocol upgrade.
+///
+/// Such transactions are not signed: they are authorized by the L1 contracts, and the
+/// transaction hash is the canonical hash calculated on L1. Hence, they are represented as
+/// [`Sealed`] transactions and cannot be sent via L2.
+///
+/// Note: `gas_per_pubdata_limit`, `to_mint`, `refund_recipient`, and `factory_deps` are not
+/// returned by the `eth_` namespace and are only set for transactions built via
+/// [`TxL1::from_raw`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[doc(alias = "L1Transaction", alias = "PriorityTransaction")]
+pub struct TxL1 {
+    /// Type of the transaction, either [`TxType::L1Priority`] or [`TxType::ProtocolUpgrade`].
+    #[serde(skip, default = "default_tx_type")]
+    pub tx_type: TxType,
+    /// Chain ID of the L2 network.
+    #[serde(with = "alloy::serde::quantity")]
+    pub chain_id: ChainId,
+    /// Serial ID of the priority operation.
+    #[serde(with = "alloy::serde::quantity")]
+    pub nonce: u64,
+    /// Gas limit of the transaction on L2.
+    #[serde(with = "alloy::serde::quantity")]
+    pub gas: u64,
+    /// Maximum fee per gas the sender paid for on L1.
+    #[serde(with = "alloy::serde::quantity")]
+    pub max_fee_per_gas: u128,
+    /// Address of the receiver of the message.
+    pub to: Address,
+    /// Address of the sender of the message. For L1 contracts, this is an aliased address.
+    pub from: Address,
+    /// Value transferred to the receiver.
+    pub value: U256,
+    /// Calldata of the message call.
+    pub input: Bytes,
+    /// Maximum amount of gas the sender is willing to pay per byte of pubdata.
+    #[serde(default)]
+    pub gas_per_pubdata_limit: U256,
+    /// Amount of the base token minted on L2 with the transaction.
+    #[serde(default)]
+    pub to_mint: U256,
+    /// Recipient of the refund for the unused gas.
+    #[serde(default)]
+    pub refund_recipient: Address,
+    /// Bytecodes of the contracts to be marked as known on L2.
+    #[serde(default)]
+    pub factory_deps: Vec<Bytes>,
+}
+
+fn default_tx_type() -> TxType {
+    TxType::L1Priority
+}
+
+impl TxL1 {
+    /// Creates an L1-originated transaction from the raw transaction returned by
+    /// `zks_getRawBlockTransactions`.
+    ///
+    /// Returns `None` for transactions originated on L2.
+    pub fn from_raw(tx: RawTransaction, chain_id: ChainId) -> Option<Sealed<Self>> {
+        let execute = tx.execute;
+        let (tx, hash) = match tx.common_data {
+            ExecuteTransactionCommon::L2(_) => return None,
+            ExecuteTransactionCommon::L1(data) => (
+                Self {
+                    tx_type: TxType::L1Priority,
+                    chain_id,
+                    nonce: data.serial_id,
+                    gas: data.gas_limit.saturating_to(),
+                    max_fee_per_gas: data.max_fee_per_gas.saturating_to(),
+                    to: execute.contract_address.unwrap_or_default(),
+                    from: data.sender,
+                    value: execute.value,
+                    input: execute.calldata,
+                    gas_per_pubdata_limit: data.gas_per_pubdata_limit,
+                    to_mint: data.to_mint,
+                    refund_recipient: data.refund_recipient,
+                    factory_deps: execute.factory_deps,
+                },
+                data.canonical_tx_hash,
+            ),
+            ExecuteTransactionCommon::ProtocolUpgrade(data) => (
+                Self {
+                    tx_type: TxType::ProtocolUpgrade,
+                    chain_id,
+                    nonce: 0,
+                    gas: data.gas_limit.saturating_to(),
+                    max_fee_per_gas: data.max_fee_per_gas.saturating_to(),
+                    to: execute.contract_address.unwrap_or_default(),
+                    from: data.sender,
+                    value: execute.value,
+                    input: execute.calldata,
+                    gas_per_pubdata_limit: data.gas_per_pubdata_limit,
+                    to_mint: data.to_mint,
+                    refund_recipient: data.refund_recipient,
+                    factory_deps: execute.factory_deps,
+                },
+                data.canonical_tx_hash,
+            ),
+        };
+        Some(Sealed::new_unchecked(tx, hash))
+    }
+
+    fn fields_len(&self) -> usize {
+        self.chain_id.length()
+            + self.nonce.length()
+            + self.gas.length()
+            + self.max_fee_per_gas.length()
+            + self.to.length()
+            + self.from.length()
+            + self.value.length()
+            + self.input.length()
+            + self.gas_per_pubdata_limit.length()
+            + self.to_mint.length()
+            + self.refund_recipient.length()
+            + self.factory_deps.length()
+    }
+
+    /// Gets the length of the encoded transaction together with its hash, excluding the type byte.
+    pub(crate) fn encoded_length(&self, hash: &B256) -> usize {
+        let payload_length = self.fields_len() + hash.length();
+        alloy::rlp::length_of_length(payload_length) + payload_length
+    }
+
+    /// Encodes the transaction together with its hash, prefixed with the type byte.
+    ///
+    /// L1-originated transactions are never sent to the node, so this encoding is specific
+    /// to this crate and only exists so that such transactions can be handled as EIP-2718 envelopes.
+    pub(crate) fn encode_sealed(&self, hash: &B256, out: &mut dyn BufMut) {
+        out.put_u8(self.tx_type as u8);
+        Header {
+            list: true,
+            payload_length: self.fields_len() + hash.length(),
+        }
+        .encode(out);
+        self.chain_id.encode(out);
+        self.nonce.encode(out);
+        self.gas.encode(out);
+        self.max_fee_per_gas.encode(out);
+        self.to.encode(out);
+        self.from.encode(out);
+        self.value.encode(out);
+        self.input.encode(out);
+        self.gas_per_pubdata_limit.encode(out);
+        self.to_mint.encode(out);
+        self.refund_recipient.encode(out);
+        self.factory_deps.encode(out);
+        hash.encode(out);
+    }
+
+    /// Decodes the transaction encoded via [`TxL1::encode_sealed`], without the type byte.
+    pub(crate) fn decode_sealed(
+        tx_type: TxType,
+        buf: &mut &[u8],
+    ) -> alloy::rlp::Result<Sealed<Self>> {
+        let header = Header::decode(buf)?;
+        if !header.list {
+            return Err(alloy::rlp::Error::UnexpectedString);
+        }
+        let original_len = buf.len();
+
+        let tx = Self {
+            tx_type,
+            chain_id: Decodable::decode(buf)?,
+            nonce: Decodable::decode(buf)?,
+            gas: Decodable::decode(buf)?,
+            max_fee_per_gas: Decodable::decode(buf)?,
+            to: Decodable::decode(buf)?,
+            from: Decodable::decode(buf)?,
+            value: Decodable::decode(buf)?,
+            input: Decodable::decode(buf)?,
+            gas_per_pubdata_limit: Decodable::decode(buf)?,
+            to_mint: Decodable::decode(buf)?,
+            refund_recipient: Decodable::decode(buf)?,
+            factory_deps: Decodable::decode(buf)?,
+        };
+        let hash = Decodable::decode(buf)?;
+
+        if buf.len() + header.payload_length != original_len {
+            return Err(alloy::rlp::Error::ListLengthMismatch {
+                expected: header.payload_length,
+                got: original_len - buf.len(),
+            });
+        }
+
+        Ok(Sealed::new_unchecked(tx, hash))
+    }
+}
+
+impl Typed2718 for TxL1 {
+    fn ty(&self) -> u8 {
+        self.tx_type as u8
+    }
+}
+
+impl Transaction for TxL1 {
+    fn chain_id(&self) -> Option<ChainId> {
+        Some(self.chain_id)
+    }
+
+    fn nonce(&self) -> u64 {
+        self.nonce
+    }
+
+    fn gas_limit(&self) -> u64 {
+        self.gas
+    }
+
+    fn gas_price(&self) -> Option<u128> {
+        None
+    }
+
+    fn max_fee_per_gas(&self) -> u128 {
+        self.max_fee_per_gas
+    }
+
+    fn max_priority_fee_per_gas(&self) -> Option<u128> {
+        Some(0)
+    }
+
+    fn max_fee_per_blob_gas(&self) -> Option<u128> {
+        None
+    }
+
+    fn priority_fee_or_price(&self) -> u128 {
+        0
+    }
+
+    fn effective_gas_price(&self, _base_fee: Option<u64>) -> u128 {
+        self.max_fee_per_gas
+    }
+
+    fn is_dynamic_fee(&self) -> bool {
+        false
+    }
+
+    fn kind(&self) -> TxKind {
+        self.to.into()
+    }
+
+    fn is_create(&self) -> bool {
+        false
+    }
+
+    fn value(&self) -> U256 {
+        self.value
+    }
+
+    fn input(&self) -> &Bytes {
+        &self.input
+    }
+
+    fn access_list(&self) -> Option<&alloy::rpc::types::AccessList> {
+        None
+    }
+
+    fn blob_versioned_hashes(&self) -> Option<&[B256]> {
+        None
+    }
+
+    fn authorization_list(&self) -> Option<&[alloy::eips::eip7702::SignedAuthorization]> {
+        None
+    }
+}
+
+impl From<TxL1> for alloy::rpc::types::transaction::TransactionRequest {
+    fn from(tx: TxL1) -> Self {
+        Self {
+            transaction_type: Some(tx.tx_type as u8),
+            chain_id: Some(tx.chain_id),
+            nonce: Some(tx.nonce),
+            gas: Some(tx.gas),
+            max_fee_per_gas: Some(tx.max_fee_per_gas),
+            max_priority_fee_per_gas: Some(0),
+            to: Some(tx.to.into()),
+            from: Some(tx.from),
+            value: Some(tx.value),
+            input: TransactionInput::new(tx.input),
+            ..Default::default()
+        }
+    }
+}
+
+/// (De)serialization of [`Sealed<TxL1>`] in the format used by the RPC, i.e. with
+/// the transaction hash in the `hash` field.
+pub(crate) mod sealed_serde {
+    use std::borrow::Cow;
+
+    use alloy::primitives::{B256, Sealed};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::TxL1;
+    use crate::network::tx_type::TxType;
+
+    #[derive(Serialize, Deserialize)]
+    struct SealedTxL1<'a> {
+        #[serde(flatten)]
+        tx: Cow<'a, TxL1>,
+        hash: B256,
+    }
+
+    pub(crate) fn serialize<S: Serializer>(
+        sealed: &Sealed<TxL1>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        SealedTxL1 {
+            tx: Cow::Borrowed(sealed.inner()),
+            hash: sealed.hash(),
+        }
+        .serialize(serializer)
+    }
+
+    fn deserialize<'de, D: Deserializer<'de>>(
+        tx_type: TxType,
+        deserializer: D,
+    ) -> Result<Sealed<TxL1>, D::Error> {
+        let SealedTxL1 { tx, hash } = SealedTxL1::deserialize(deserializer)?;
+        let tx = TxL1 {
+            tx_type,
+            ..tx.into_owned()
+        };
+        Ok(Sealed::new_unchecked(tx, hash))
+    }
+
+    pub(crate) fn deserialize_l1_priority<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Sealed<TxL1>, D::Error> {
+        deserialize(TxType::L1Priority, deserializer)
+    }
+
+    pub(crate) fn deserialize_protocol_upgrade<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Sealed<TxL1>, D::Error> {
+        deserialize(TxType::ProtocolUpgrade, deserializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+    use crate::network::tx_envelope::TxEnvelope;
+    use crate::types::{Execute, L1TxCommonData, OpProcessingType, PriorityQueueType};
+    use alloy::network::eip2718::{Decodable2718, Encodable2718};
+    use alloy::primitives::address;
+
+    #[test]
+    fn from_raw_l1_tx() {
+        let canonical_tx_hash =
+            B256::from_str("0x0bd9e3a4db1ee4d9ce5a6c9cbd0b2a5ec4ee6e5fbd3c4b7f8ff8d44e0a1bdd4b")
+                .unwrap();
+        let raw_tx = RawTransaction {
+            common_data: ExecuteTransactionCommon::L1(L1TxCommonData {
+                sender: address!("1111111111111111111111111111111111111111"),
+                serial_id: 42,
+                layer_2_tip_fee: U256::ZERO,
+                full_fee: U256::ZERO,
+                max_fee_per_gas: U256::from(250000000),
+                gas_limit: U256::from(300000),
+                gas_per_pubdata_limit: U256::from(800),
+                op_processing_type: OpProcessingType::Common,
+                priority_queue_type: PriorityQueueType::Deque,
+                canonical_tx_hash,
+                to_mint: U256::from(1000),
+                refund_recipient: address!("2222222222222222222222222222222222222222"),
+            }),
+            execute: Execute {
+                contract_address: Some(address!("3333333333333333333333333333333333333333")),
+                calldata: Bytes::from_str("0x010203").unwrap(),
+                value: U256::from(10),
+                factory_deps: vec![],
+            },
+            received_timestamp_ms: 0,
+            raw_bytes: None,
+        };
+
+        let sealed = TxL1::from_raw(raw_tx, 270).unwrap();
+        assert_eq!(sealed.hash(), canonical_tx_hash);
+        let tx = sealed.inner();
+        assert_eq!(tx.ty(), 0xff);
+        assert_eq!(tx.nonce, 42);
+        assert_eq!(tx.gas, 300000);
+        assert_eq!(
+            tx.from,
+            address!("1111111111111111111111111111111111111111")
+        );
+        assert_eq!(tx.to_mint, U256::from(1000));
+
+        let mut buf = vec![];
+        tx.encode_sealed(sealed.hash_ref(), &mut buf);
+        assert_eq!(buf.len(), 1 + tx.encoded_length(sealed.hash_ref()));
+        let decoded = TxL1::decode_sealed(TxType::L1Priority, &mut &buf[1..]).unwrap();
+        assert_eq!(decoded, sealed);
+    }
+
+    #[test]
+    fn from_raw_protocol_upgrade_tx() {
+        // Protocol upgrade transaction as returned by `zks_getRawBlockTransactions`.
+        let raw_tx: RawTransaction = serde_json::from_value(serde_json::json!({
+            "common_data": {
+                "ProtocolUpgrade": {
+                    "sender": "0x0000000000000000000000000000000000008007",
+                    "upgradeId": 25,
+                    "maxFeePerGas": "0xee6b280",
+                    "gasLimit": "0x4c4b400",
+                    "gasPerPubdataLimit": "0x320",
+                    "ethBlock": 6734871,
+                    "canonicalTxHash": "0x8b65c0cf1012ea9f393197eb24619fd814379b298b238285649e14f936a5eb12",
+                    "toMint": "0x0",
+                    "refundRecipient": "0x0000000000000000000000000000000000008007"
+                }
+            },
+            "execute": {
+                "contractAddress": "0x0000000000000000000000000000000000008006",
+                "calldata": "0xe9f18c17",
+                "value": "0x0",
+                "factoryDeps": []
+            },
+            "received_timestamp_ms": 1727779200000_u64,
+            "raw_bytes": null
+        }))
+        .unwrap();
+
+        let sealed = TxL1::from_raw(raw_tx, 270).unwrap();
+        assert_eq!(
+            sealed.hash(),
+            B256::from_str("0x8b65c0cf1012ea9f393197eb24619fd814379b298b238285649e14f936a5eb12")
+                .unwrap()
+        );
+        let tx = sealed.inner();
+        assert_eq!(tx.ty(), 0xfe);
+        assert_eq!(tx.nonce, 0);
+        assert_eq!(tx.gas, 80000000);
+        assert_eq!(tx.gas_per_pubdata_limit, U256::from(800));
+        assert_eq!(
+            tx.from,
+            address!("0000000000000000000000000000000000008007")
+        );
+        assert_eq!(tx.to, address!("0000000000000000000000000000000000008006"));
+
+        let envelope = TxEnvelope::ProtocolUpgrade(sealed);
+        let encoded = envelope.encoded_2718();
+        assert_eq!(encoded[0], 0xfe);
+        let decoded = TxEnvelope::decode_2718(&mut encoded.as_slice()).unwrap();
+        assert_eq!(decoded, envelope);
+
+        let json = serde_json::to_value(&envelope).unwrap();
+        assert_eq!(json["type"], "0xfe");
+        let deserialized: TxEnvelope = serde_json::from_value(json).unwrap();
+        assert_eq!(deserialized, envelope);
+    }
+}
@@ -1,6 +1,8 @@
 use eip712::TxEip712;
+use l1::TxL1;
 
 pub mod eip712;
+pub mod l1;
 
 /// ZKsync transaction type.
 #[derive(Debug)]
@@ -9,6 +11,10 @@ pub enum TypedTransaction {
     Native(alloy::consensus::TypedTransaction),
     /// ZKsync-specific EIP-712 transaction type.
     Eip712(TxEip712),
+    /// ZKsync-specific L1 priority operation.
+    L1Priority(TxL1),
+    /// ZKsync-specific protocol upgrade transaction.
+    ProtocolUpgrade(TxL1),
 }
 
 impl From<crate::network::tx_envelope::TxEnvelope> for TypedTransaction {
@@ -16,6 +22,10 @@ impl From<crate::network::tx_envelope::TxEnvelope> for TypedTransaction {
         match value {
             crate::network::tx_envelope::TxEnvelope::Native(inner) => Self::Native(inner.into()),
             super::tx_envelope::TxEnvelope::Eip712(signed) => Self::Eip712(signed.into_parts().0),
+            super::tx_envelope::TxEnvelope::L1Priority(sealed) => Self::L1Priority(sealed.unseal()),
+            super::tx_envelope::TxEnvelope::ProtocolUpgrade(sealed) => {
+                Self::ProtocolUpgrade(sealed.unseal())
+            }
         }
     }
 }
@@ -0,0 +1,190 @@
+//! Implementation of the logic to claim failed deposits.
+
+use crate::{
+    contracts::{l1::l1_bridge::L1Bridge, l2::l2_bridge::L2Bridge::finalizeDepositCall},
+    provider::{L1CommunicationError, ZksyncProvider},
+    types::ExecuteTransactionCommon,
+    utils::BOOTLOADER_FORMAL_ADDRESS,
+};
+use alloy::{
+    network::{Ethereum, ReceiptResponse as _},
+    primitives::{Address, B256, U256},
+    providers::Provider,
+    rpc::types::eth::TransactionReceipt,
+    sol_types::SolCall,
+};
+
+/// Parameters required to claim the failed deposit on L1.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ClaimFailedDepositParams {
+    /// Initiator of the deposit on L1.
+    pub deposit_sender: Address,
+    /// Deposited token address on L1.
+    pub l1_token: Address,
+    /// Deposited amount.
+    pub amount: U256,
+    /// L1 batch number where the deposit was processed.
+    pub l1_batch_number: u64,
+    /// Position of the deposit status log in the L2 logs Merkle tree.
+    pub l2_message_index: u32,
+    /// Number of the deposit transaction in the L1 batch.
+    pub l2_tx_number_in_batch: u16,
+    /// Merkle proof of the deposit status log.
+    pub proof: Vec<B256>,
+}
+
+/// Type that handles claiming of the failed deposits on L1.
+pub struct ClaimFailedDepositExecutor<'a, P1, P2>
+where
+    P1: Provider<Ethereum>,
+    P2: ZksyncProvider + ?Sized,
+{
+    l1_provider: &'a P1,
+    l2_provider: &'a P2,
+    l2_tx_hash: B256,
+}
+
+impl<'a, P1, P2> ClaimFailedDepositExecutor<'a, P1, P2>
+where
+    P1: Provider<Ethereum>,
+    P2: ZksyncProvider + ?Sized,
+{
+    /// Prepares an executor for claiming the deposit with the provided L2 transaction hash.
+    pub fn new(l1_provider: &'a P1, l2_provider: &'a P2, l2_tx_hash: B256) -> Self {
+        ClaimFailedDepositExecutor {
+            l1_provider,
+            l2_provider,
+            l2_tx_hash,
+        }
+    }
+
+    /// Gathers the parameters required to claim the failed deposit on L1.
+    ///
+    /// Fails if the deposit did not fail on L2, or if the L1 batch containing the deposit
+    /// is not yet processed and the L2 -> L1 log proof is not available.
+    pub async fn get_claim_failed_deposit_params(
+        &self,
+    ) -> Result<ClaimFailedDepositParams, L1CommunicationError> {
+        let receipt = self
+            .l2_provider
+            .get_transaction_receipt(self.l2_tx_hash)
+            .await
+            .map_err(L1CommunicationError::rpc(
+                "fetching the deposit transaction receipt",
+            ))?
+            .ok_or(L1CommunicationError::TransactionNotFound(self.l2_tx_hash))?;
+        if receipt.status() {
+            return Err(L1CommunicationError::DepositNotFailed(self.l2_tx_hash));
+        }
+
+        let (l2_to_l1_log_index, _) = receipt
+            .l2_to_l1_logs()
+            .iter()
+            .enumerate()
+            .find(|(_, log)| log.sender == BOOTLOADER_FORMAL_ADDRESS && log.key == self.l2_tx_hash)
+            .ok_or(L1CommunicationError::L2ToL1LogNotFound(self.l2_tx_hash))?;
+
+        let log_proof = self
+            .l2_provider
+            .get_l2_to_l1_log_proof(self.l2_tx_hash, Some(l2_to_l1_log_index))
+            .await
+            .map_err(L1CommunicationError::rpc("fetching the L2 -> L1 log proof"))?
+            .ok_or(L1CommunicationError::BatchNotProcessed(self.l2_tx_hash))?;
+
+        let l1_batch_number = receipt
+            .l1_batch_number()
+            .ok_or(L1CommunicationError::BatchNotProcessed(self.l2_tx_hash))?
+            .to::<u64>();
+        let l2_tx_number_in_batch = receipt
+            .l1_batch_tx_index()
+            .ok_or(L1CommunicationError::BatchNotProcessed(self.l2_tx_hash))?
+            .try_into()
+            .map_err(L1CommunicationError::invalid_data(
+                "converting the deposit transaction index in the L1 batch",
+            ))?;
+
+        let block_number = receipt
+            .block_number()
+            .ok_or(L1CommunicationError::BatchNotProcessed(self.l2_tx_hash))?;
+        let deposit_calldata = self
+            .l2_provider
+            .get_raw_block_transactions(block_number)
+            .await
+            .map_err(L1CommunicationError::rpc("fetching block transactions"))?
+            .into_iter()
+            .find_map(|tx| match tx.common_data {
+                ExecuteTransactionCommon::L1(common_data)
+                    if common_data.canonical_tx_hash == self.l2_tx_hash =>
+                {
+                    Some(tx.execute.calldata)
+                }
+                _ => None,
+            })
+            .ok_or(L1CommunicationError::TransactionNotFound(self.l2_tx_hash))?;
+        let finalize_deposit = finalizeDepositCall::abi_decode(&deposit_calldata).map_err(
+            L1CommunicationError::invalid_data("decoding the finalizeDeposit calldata"),
+        )?;
+
+        Ok(ClaimFailedDepositParams {
+            deposit_sender: finalize_deposit._l1Sender,
+            l1_token: finalize_deposit._l1Token,
+            amount: finalize_deposit._amount,
+            l1_batch_number,
+            l2_message_index: log_proof.id,
+            l2_tx_number_in_batch,
+            proof: log_proof.proof,
+        })
+    }
+
+    /// Executes the claim of the failed deposit. This will handle:
+    /// - Recovering the deposit parameters from the L2 transaction.
+    /// - Gathering the L2 -> L1 log proof of the failed deposit status.
+    /// - Sending the `claimFailedDeposit` transaction to the L1 shared bridge.
+    ///
+    /// ## Returns
+    ///
+    /// TransactionReceipt of the L1 claim transaction.
+    pub async fn execute(&self) -> Result<TransactionReceipt, L1CommunicationError> {
+        let params = self.get_claim_failed_deposit_params().await?;
+
+        let bridge_addresses = self
+            .l2_provider
+            .get_bridge_contracts()
+            .await
+            .map_err(L1CommunicationError::rpc("fetching bridge contracts"))?;
+        let l1_bridge_address = bridge_addresses.l1_shared_default_bridge.ok_or(
+            L1CommunicationError::MissingBridgeConfiguration(
+                "L1 shared default bridge is not defined for the chain.",
+            ),
+        )?;
+        let l2_chain_id = self
+            .l2_provider
+            .get_chain_id()
+            .await
+            .map_err(L1CommunicationError::rpc("fetching L2 chain id"))?;
+
+        let l1_bridge = L1Bridge::new(l1_bridge_address, self.l1_provider);
+        l1_bridge
+            .claimFailedDeposit(
+                U256::from(l2_chain_id),
+                params.deposit_sender,
+                params.l1_token,
+                params.amount,
+                self.l2_tx_hash,
+                U256::from(params.l1_batch_number),
+                U256::from(params.l2_message_index),
+                params.l2_tx_number_in_batch,
+                params.proof,
+            )
+            .send()
+            .await
+            .map_err(L1CommunicationError::contract(
+                "sending the L1 failed deposit claim transaction",
+            ))?
+            .get_receipt()
+            .await
+            .map_err(L1CommunicationError::pending(
+                "getting the L1 failed deposit claim transaction receipt",
+            ))
+    }
+}
@@ -0,0 +1,314 @@
+//! Deployment of EraVM contracts.
+
+use crate::{
+    artifacts::{ArtifactError, Artifacts},
+    contracts::l2::contract_deployer::{CONTRACT_DEPLOYER_ADDRESS, ContractDeployed},
+    network::{
+        Zksync,
+        transaction_request::TransactionRequest,
+        unsigned_tx::eip712::{BytecodeHashError, hash_bytecode},
+    },
+    provider::ZksyncProvider,
+};
+use alloy::{
+    contract::{ContractInstance, Interface},
+    json_abi::JsonAbi,
+    network::{ReceiptResponse as _, TransactionBuilder},
+    primitives::{Address, B256, Bytes, U256},
+    providers::{PendingTransactionError, Provider},
+    rpc::types::Log,
+    transports::TransportError,
+};
+
+/// Errors that can occur during the contract deployment.
+#[derive(Debug, thiserror::Error)]
+pub enum DeployError {
+    /// Bytecode of the contract or its factory dependencies cannot be hashed.
+    #[error(transparent)]
+    BytecodeHash(#[from] BytecodeHashError),
+    /// Deployment transaction could not be sent.
+    #[error("Failed to send the deployment transaction: {0}")]
+    Rpc(#[from] TransportError),
+    /// Deployment transaction could not be tracked until its inclusion.
+    #[error("Failed to get the deployment transaction receipt: {0}")]
+    Pending(#[from] PendingTransactionError),
+    /// Deployment transaction was reverted.
+    #[error("Deployment transaction {0} was reverted")]
+    Reverted(B256),
+    /// Deployment transaction succeeded but didn't emit the `ContractDeployed` event,
+    /// so the address of the deployed contract is unknown.
+    #[error("ContractDeployed event log was not found in the deployment transaction {0}")]
+    ContractDeployedLogNotFound(B256),
+    /// EVM bytecode cannot be deployed, as the EVM emulator is not enabled on the chain.
+    #[error("EVM emulator is not enabled on the chain")]
+    EvmEmulatorDisabled,
+}
+
+/// Factory for deploying EraVM contracts via the `ContractDeployer` system contract.
+///
+/// This is the ZKsync counterpart of the `deploy` methods generated by `sol!`, which send EVM
+/// create transactions that are not supported by EraVM. EVM bytecode can still be deployed
+/// via [`ContractFactory::evm`] if the chain has the EVM emulator enabled.
+///
+/// ```ignore
+/// let counter = ContractFactory::new(bytecode)
+///     .deploy_address(&provider)
+///     .await?;
+/// let counter = Counter::new(counter, &provider);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ContractFactory {
+    abi: JsonAbi,
+    bytecode: Vec<u8>,
+    factory_deps: Vec<Vec<u8>>,
+    constructor_args: Bytes,
+    salt: Option<B256>,
+    value: U256,
+    evm: bool,
+}
+
+impl ContractFactory {
+    /// Creates a factory for the contract with the provided EraVM bytecode.
+    pub fn new(bytecode: impl Into<Vec<u8>>) -> Self {
+        Self {
+            abi: JsonAbi::default(),
+            bytecode: bytecode.into(),
+            factory_deps: Vec::new(),
+            constructor_args: Bytes::new(),
+            salt: None,
+            value: U256::ZERO,
+            evm: false,
+        }
+    }
+
+    /// Creates a factory for the contract with the provided EVM creation bytecode, e.g. produced
+    /// by `solc`. Such contracts can only be deployed on chains with the EVM emulator enabled.
+    ///
+    /// Factory dependencies are ignored for EVM contracts.
+    pub fn evm(init_code: impl Into<Vec<u8>>) -> Self {
+        Self {
+            evm: true,
+            ..Self::new(init_code)
+        }
+    }
+
+    /// Creates a factory for the contract with the given name from the artifacts,
+    /// with the factory dependencies resolved from the same artifacts.
+    pub fn from_artifact(artifacts: &Artifacts, name: &str) -> Result<Self, ArtifactError> {
+        let artifact = artifacts
+            .get(name)
+            .ok_or_else(|| ArtifactError::ContractNotFound(name.to_owned()))?;
+        let factory_deps = artifacts
+            .resolve_factory_deps(artifact)?
+            .into_iter()
+            .map(Into::into)
+            .collect();
+        Ok(Self::new(artifact.bytecode.to_vec())
+            .with_abi(artifact.abi.clone())
+            .with_factory_deps(factory_deps))
+    }
+
+    /// Sets the ABI of the contract, used by [`Self::deploy`] to create the contract instance.
+    pub fn with_abi(mut self, abi: JsonAbi) -> Self {
+        self.abi = abi;
+        self
+    }
+
+    /// Sets the factory dependencies of the contract, i.e. bytecodes of the contracts
+    /// it can deploy.
+    pub fn with_factory_deps(mut self, factory_deps: Vec<Vec<u8>>) -> Self {
+        self.factory_deps = factory_deps;
+        self
+    }
+
+    /// Sets the ABI-encoded constructor arguments.
+    pub fn with_constructor_args(mut self, constructor_args: impl Into<Bytes>) -> Self {
+        self.constructor_args = constructor_args.into();
+        self
+    }
+
+    /// Sets the salt, so that the contract is deployed via `create2`.
+    pub fn with_salt(mut self, salt: B256) -> Self {
+        self.salt = Some(salt);
+        self
+    }
+
+    /// Sets the value passed to the constructor.
+    pub fn with_value(mut self, value: U256) -> Self {
+        self.value = value;
+        self
+    }
+
+    /// Returns the deployment transaction request.
+    pub fn request(&self) -> Result<TransactionRequest, BytecodeHashError> {
+        let request = TransactionRequest::default().with_value(self.value);
+        if self.evm {
+            let init_code = [self.bytecode.as_slice(), &self.constructor_args].concat();
+            return Ok(match self.salt {
+                Some(salt) => request.with_create2_evm_params(salt, init_code),
+                None => request.with_create_evm_params(init_code),
+            });
+        }
+        match self.salt {
+            Some(salt) => request.with_create2_params(
+                salt,
+                self.bytecode.clone(),
+                self.constructor_args.to_vec(),
+                self.factory_deps.clone(),
+            ),
+            None => request.with_create_params(
+                self.bytecode.clone(),
+                self.constructor_args.to_vec(),
+                self.factory_deps.clone(),
+            ),
+        }
+    }
+
+    /// Deploys the contract and returns its address.
+    ///
+    /// The address is taken from the `ContractDeployed` event emitted for the deployed bytecode
+    /// on behalf of the transaction sender.
+    ///
+    /// For EVM contracts, checks that the EVM emulator is enabled before sending the transaction.
+    pub async fn deploy_address<P>(&self, provider: &P) -> Result<Address, DeployError>
+    where
+        P: Provider<Zksync>,
+    {
+        let bytecode_hash = if self.evm {
+            let protocol_version = provider.get_protocol_version(None).await?;
+            if !protocol_version.is_some_and(|version| version.is_evm_emulator_enabled()) {
+                return Err(DeployError::EvmEmulatorDisabled);
+            }
+            // Hash of the deployed bytecode is only known after the deployment.
+            None
+        } else {
+            Some(B256::from(hash_bytecode(&self.bytecode)?))
+        };
+        let receipt = provider
+            .send_transaction(self.request()?)
+            .await?
+            .get_receipt()
+            .await?;
+        let tx_hash = receipt.transaction_hash();
+        if !receipt.status() {
+            return Err(DeployError::Reverted(tx_hash));
+        }
+
+        deployed_address(receipt.logs(), receipt.from(), bytecode_hash)
+            .ok_or(DeployError::ContractDeployedLogNotFound(tx_hash))
+    }
+
+    /// Deploys the contract and returns the contract instance bound to its address,
+    /// using the ABI of the factory.
+    ///
+    /// For the contracts generated by `sol!`, use [`Self::deploy_address`] and bind the instance
+    /// via the generated `new` method instead.
+    pub async fn deploy<P>(&self, provider: P) -> Result<ContractInstance<P, Zksync>, DeployError>
+    where
+        P: Provider<Zksync>,
+    {
+        let address = self.deploy_address(&provider).await?;
+        Ok(ContractInstance::new(
+            address,
+            provider,
+            Interface::new(self.abi.clone()),
+        ))
+    }
+}
+
+/// Finds the address of the contract with the given bytecode hash deployed by `deployer`.
+/// If the hash is not provided (EVM contracts), looks for the first EVM contract instead.
+/// Logs of the contracts deployed by the constructor are skipped.
+fn deployed_address(
+    logs: &[Log],
+    deployer: Address,
+    bytecode_hash: Option<B256>,
+) -> Option<Address> {
+    logs.iter()
+        .filter(|log| log.address() == CONTRACT_DEPLOYER_ADDRESS)
+        .filter_map(|log| log.log_decode::<ContractDeployed>().ok())
+        .map(|log| log.inner.data)
+        .find(|event| {
+            event.deployerAddress == deployer
+                && match bytecode_hash {
+                    Some(hash) => event.bytecodeHash == hash,
+                    None => event.bytecodeHash[0] == EVM_BYTECODE_HASH_VERSION,
+                }
+        })
+        .map(|event| event.contractAddress)
+}
+
+/// Version byte of the EVM bytecode hashes, see [`hash_evm_bytecode`](crate::network::unsigned_tx::eip712::hash_evm_bytecode).
+const EVM_BYTECODE_HASH_VERSION: u8 = 2;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contracts::l2::contract_deployer::{create2Call, createCall, createEVMCall};
+    use crate::network::unsigned_tx::eip712::hash_evm_bytecode;
+    use alloy::{
+        primitives::{LogData, address},
+        sol_types::SolCall,
+    };
+
+    fn deployed_log(deployer: Address, bytecode_hash: B256, contract: Address) -> Log {
+        let event = ContractDeployed {
+            deployerAddress: deployer,
+            bytecodeHash: bytecode_hash,
+            contractAddress: contract,
+        };
+        Log {
+            inner: alloy::primitives::Log {
+                address: CONTRACT_DEPLOYER_ADDRESS,
+                data: LogData::from(&event),
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn request_uses_create_or_create2() {
+        let factory = ContractFactory::new(vec![1; 32]).with_constructor_args(vec![2, 3]);
+        let request = factory.request().unwrap();
+        let call = createCall::abi_decode(request.input().unwrap()).unwrap();
+        assert_eq!(call.constructorInput, Bytes::from(vec![2, 3]));
+
+        let salt = B256::repeat_byte(4);
+        let request = factory.with_salt(salt).request().unwrap();
+        let call = create2Call::abi_decode(request.input().unwrap()).unwrap();
+        assert_eq!(call.salt, salt);
+
+        let request = ContractFactory::evm(vec![0x60, 0x80])
+            .with_constructor_args(vec![2, 3])
+            .request()
+            .unwrap();
+        let call = createEVMCall::abi_decode(request.input().unwrap()).unwrap();
+        assert_eq!(call.initCode, Bytes::from(vec![0x60, 0x80, 2, 3]));
+        assert_eq!(request.to(), Some(CONTRACT_DEPLOYER_ADDRESS));
+    }
+
+    #[test]
+    fn finds_deployed_address() {
+        let deployer = address!("36615Cf349d7F6344891B1e7CA7C72883F5dc049");
+        let contract = address!("4B5DF730c2e6b28E17013A1485E5d9BC41Efe021");
+        let child = address!("1111111111111111111111111111111111111111");
+        let bytecode_hash = B256::from(hash_bytecode(&[1; 32]).unwrap());
+        let child_hash = B256::from(hash_bytecode(&[2; 32]).unwrap());
+
+        let logs = [
+            // Constructor of the contract deploys a child contract.
+            deployed_log(contract, child_hash, child),
+            deployed_log(deployer, bytecode_hash, contract),
+        ];
+        assert_eq!(
+            deployed_address(&logs, deployer, Some(bytecode_hash)),
+            Some(contract)
+        );
+        assert_eq!(deployed_address(&logs, deployer, Some(child_hash)), None);
+        assert_eq!(deployed_address(&logs, deployer, None), None);
+
+        let evm_hash = B256::from(hash_evm_bytecode(&[0x60, 0x80]).unwrap());
+        let logs = [deployed_log(deployer, evm_hash, contract)];
+        assert_eq!(deployed_address(&logs, deployer, None), Some(contract));
+    }
+}
@@ -2,33 +2,33 @@
 
 use crate::{
     contracts::{
-        common::erc20::{ERC20, encode_token_data_for_bridge},
+        common::erc20::{ERC20, encode_token_data, encode_token_data_for_bridge},
         l1::{
-            bridge_hub::{
-                Bridgehub::{self},
-                L2TransactionRequestDirect, L2TransactionRequestTwoBridges,
-            },
+            bridge_hub::{L2TransactionRequestDirect, L2TransactionRequestTwoBridges},
             l1_bridge::{L1Bridge, encode_deposit_token_calldata},
         },
         l2::l2_bridge::encode_finalize_deposit_calldata,
     },
     network::{Zksync, transaction_request::TransactionRequest},
     provider::{
-        L1CommunicationError, ZksyncProvider, l1_transaction_receipt::L1TransactionReceipt,
+        L1CommunicationError, ZksyncProvider,
+        l1_to_l2::{
+            BridgeL2TxFeeParams, FeeParams, REQUIRED_L1_TO_L2_GAS_PER_PUBDATA_LIMIT,
+            approve_tokens, get_bridge_l2_tx_fee_params, get_bridgehub_contract, get_l1_fee_params,
+            get_l1_tx_gas_limit, get_required_approval, submit_l1_to_l2_tx,
+        },
+        l1_transaction_receipt::L1TransactionReceipt,
     },
-    utils::{ETHER_L1_ADDRESS, apply_l1_to_l2_alias},
+    utils::{ETH_ADDRESS_IN_CONTRACTS, ETHER_L1_ADDRESS, apply_l1_to_l2_alias, is_eth_l1_address},
 };
 use alloy::{
-    eips::eip1559::Eip1559Estimation,
     network::{Ethereum, NetworkWallet, TransactionBuilder},
     primitives::{Address, Bytes, U256},
-    providers::{WalletProvider, utils::Eip1559Estimator},
+    providers::WalletProvider,
     rpc::types::eth::TransactionRequest as L1TransactionRequest,
 };
 use std::str::FromStr;
 
-pub const REQUIRED_L1_TO_L2_GAS_PER_PUBDATA_LIMIT: u64 = 800;
-
 /// Type for deposit request.
 /// This type only stores the required information for the deposit, while the deposit itself
 /// is performed via [`DepositExecutor`].
@@ -38,7 +38,8 @@ pub struct DepositRequest {
     pub amount: U256,
     /// Receiver of deposited assets. If None, the sender address will be used as a receiver.
     pub receiver: Option<Address>,
-    /// L1 token address to deposit.
+    /// L1 token address to deposit. Both `ETHER_L1_ADDRESS` and `ETH_ADDRESS_IN_CONTRACTS`
+    /// can be used to deposit ETH, including chains with a custom base token.
     pub token: Address,
     /// Bridge address for the deposit. If None, default shared bridge will be used.
     pub bridge_address: Option<Address>,
@@ -100,37 +101,76 @@ impl DepositRequest {
 }
 
 #[derive(Clone, Debug, Copy)]
-struct FeeParams {
-    max_fee_per_gas: u128,
-    max_priority_fee_per_gas: u128,
+struct BridgeAddresses {
+    pub l1_bridge_address: Address,
+    pub l2_bridge_address: Address,
 }
 
-#[derive(Clone, Debug, Copy)]
-struct BridgeL2TxFeeParams {
-    pub gas_limit: U256,
-    pub tx_base_cost: U256,
+/// Token approval required before the deposit can be submitted.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DepositApproval {
+    /// L1 token to approve.
+    pub token: Address,
+    /// Address the tokens should be approved to.
+    pub spender: Address,
+    /// Allowance required for the deposit, i.e. the amount to approve.
+    /// It replaces the current allowance rather than adding to it.
+    pub amount: U256,
+    /// Current allowance of the spender, which is lower than the required one.
+    pub allowance: U256,
 }
 
+/// Estimated cost of the deposit, computed without sending any L1 transaction.
+#[derive(Clone, Debug)]
+pub struct DepositQuote {
+    /// Gas limit of the L1 deposit transaction.
+    /// None if token approvals are required, as the deposit transaction can't be estimated before they are sent.
+    pub l1_gas_limit: Option<u64>,
+    /// Max fee per gas of the L1 transactions.
+    pub max_fee_per_gas: u128,
+    /// Max priority fee per gas of the L1 transactions.
+    pub max_priority_fee_per_gas: u128,
+    /// Gas limit of the L2 transaction.
+    pub l2_gas_limit: U256,
+    /// Base cost of the L2 transaction, as returned by `l2TransactionBaseCost`.
+    pub l2_tx_base_cost: U256,
+    /// Amount of the base token minted on L2.
+    pub mint_value: U256,
+    /// Value of the L1 deposit transaction.
+    pub l1_value: U256,
+    /// Approvals that have to be sent before the deposit.
+    pub approvals: Vec<DepositApproval>,
+    /// L1 deposit transaction request.
+    pub l1_tx_request: L1TransactionRequest,
+}
+
+struct L1DepositTx {
+    tx_request: L1TransactionRequest,
+    l2_tx_fee: BridgeL2TxFeeParams,
+    mint_value: U256,
+}
+
+/// Describes how the deposit is routed based on the chain base token and the deposited token.
 #[derive(Clone, Debug, Copy)]
-struct BridgeAddresses {
-    pub l1_bridge_address: Address,
-    pub l2_bridge_address: Address,
+struct DepositRoute {
+    /// L1 address of the chain base token.
+    base_token: Address,
+    /// Whether the chain uses ETH as the base token.
+    is_eth_based_chain: bool,
+    /// Whether the deposited token is the chain base token.
+    is_base_token_deposit: bool,
+    /// Whether the deposited token is ETH.
+    is_eth_deposit: bool,
+    /// L1 address that collects the base token when it's not ETH.
+    base_token_spender: Option<Address>,
 }
 
-/// Scales the gas limit to ensure the transaction will be accepted.
-// Gas limit scaling logic is taken from the JS SDK:
-// https://github.com/zksync-sdk/zksync-ethers/blob/64763688d1bb5cee4a4c220c3841b803c74b0d05/src/utils.ts#L1451
-pub fn scale_l1_gas_limit(l1_gas_limit: u64) -> u64 {
-    /// Numerator used in scaling the gas limit to ensure acceptance of `L1->L2` transactions.
-    /// This constant is part of a coefficient calculation to adjust the gas limit to account for variations
-    /// in the SDK estimation, ensuring the transaction will be accepted.
-    const L1_FEE_ESTIMATION_COEF_NUMERATOR: u64 = 12;
-
-    /// Denominator used in scaling the gas limit to ensure acceptance of `L1->L2` transactions.
-    /// This constant is part of a coefficient calculation to adjust the gas limit to account for variations
-    /// in the SDK estimation, ensuring the transaction will be accepted.
-    const L1_FEE_ESTIMATION_COEF_DENOMINATOR: u64 = 10;
-    l1_gas_limit * L1_FEE_ESTIMATION_COEF_NUMERATOR / L1_FEE_ESTIMATION_COEF_DENOMINATOR
+struct PreparedDeposit {
+    sender: Address,
+    route: DepositRoute,
+    bridge_addresses: Option<BridgeAddresses>,
+    fee_params: FeeParams,
+    l1_deposit_tx: L1DepositTx,
 }
 
 /// Type that handles deposit logic for various scenarios: deposit ETH, ERC20 etc.
@@ -138,6 +178,7 @@ pub struct DepositExecutor<'a, P1, P2>
 where
     P1: alloy::providers::Provider<Ethereum>,
     P2: ZksyncProvider + WalletProvider<Zksync> + ?Sized,
+    P2::Wallet: NetworkWallet<Ethereum>,
 {
     l1_provider: &'a P1,
     l2_provider: &'a P2,
@@ -148,6 +189,7 @@ impl<'a, P1, P2> DepositExecutor<'a, P1, P2>
 where
     P1: alloy::providers::Provider<Ethereum>,
     P2: ZksyncProvider + WalletProvider<Zksync> + ?Sized,
+    P2::Wallet: NetworkWallet<Ethereum>,
 {
     /// Prepares an executor for a particular deposit request.
     pub fn new(l1_provider: &'a P1, l2_provider: &'a P2, request: &'a DepositRequest) -> Self {
@@ -169,26 +211,23 @@ where
                     .l2BridgeAddress(l2_chain_id)
                     .call()
                     .await
-                    .map_err(|_| {
-                        L1CommunicationError::Custom("Error while getting L2 bridge address.")
-                    })?;
+                    .map_err(L1CommunicationError::contract("getting L2 bridge address"))?;
                 (l1_bridge_address, l2_bridge_address)
             }
             None => {
-                let bridge_addresses =
-                    self.l2_provider.get_bridge_contracts().await.map_err(|_| {
-                        L1CommunicationError::Custom(
-                            "Error occurred while fetching bridge contracts.",
-                        )
-                    })?;
+                let bridge_addresses = self
+                    .l2_provider
+                    .get_bridge_contracts()
+                    .await
+                    .map_err(L1CommunicationError::rpc("fetching bridge contracts"))?;
                 (
                     bridge_addresses.l1_shared_default_bridge.ok_or(
-                        L1CommunicationError::Custom(
+                        L1CommunicationError::MissingBridgeConfiguration(
                             "L1 shared default bridge is not defined for the chain and bridge address is not specified in the deposit request.",
                         ),
                     )?,
                     bridge_addresses.l2_shared_default_bridge.ok_or(
-                        L1CommunicationError::Custom(
+                        L1CommunicationError::MissingBridgeConfiguration(
                             "L2 shared default bridge is not defined for the chain.",
                         ),
                     )?,
@@ -198,92 +237,41 @@ where
         Ok((l1_bridge_address, l2_bridge_address))
     }
 
-    async fn get_l1_fee_params(&self) -> Result<FeeParams, L1CommunicationError> {
-        let max_priority_fee_per_gas = self
-            .l1_provider
-            .get_max_priority_fee_per_gas()
-            .await
-            .map_err(|_| {
-                L1CommunicationError::Custom(
-                    "Error occurred while fetching L1 max_priority_fee_per_gas.",
-                )
-            })?;
-        // fees adjustment is taken from the JS SDK:
-        // https://github.com/zksync-sdk/zksync-ethers/blob/64763688d1bb5cee4a4c220c3841b803c74b0d05/src/adapters.ts#L2069
-        let base_l1_fees_data = self
-            .l1_provider
-            .estimate_eip1559_fees_with(Eip1559Estimator::new(|base_fee_per_gas, _| {
-                Eip1559Estimation {
-                    max_fee_per_gas: base_fee_per_gas * 3 / 2,
-                    max_priority_fee_per_gas: 0,
-                }
-            }))
-            .await
-            .map_err(|_| {
-                L1CommunicationError::Custom("Error occurred while estimating L1 base fees.")
-            })?;
-        let max_fee_per_gas = base_l1_fees_data.max_fee_per_gas + max_priority_fee_per_gas;
-
-        Ok(FeeParams {
-            max_fee_per_gas,
-            max_priority_fee_per_gas,
-        })
-    }
-
-    async fn get_l1_tx_gas_limit(
-        &self,
-        tx_request: &L1TransactionRequest,
-    ) -> Result<u64, L1CommunicationError> {
-        let l1_tx_gas_estimation = self
-            .l1_provider
-            .estimate_gas(tx_request.clone())
-            .await
-            .map_err(|_| {
-                L1CommunicationError::Custom(
-                    "Error occurred while estimating gas limit for the L1 transaction.",
-                )
-            })?;
-        let l1_gas_limit = scale_l1_gas_limit(l1_tx_gas_estimation);
-        Ok(l1_gas_limit)
-    }
-
-    async fn get_bridge_l2_tx_fee_params<P>(
-        &self,
-        bridge_hub_contract: &Bridgehub::BridgehubInstance<&P>,
-        l1_to_l2_tx: TransactionRequest,
-        l2_chain_id: U256,
-        fee_params: &FeeParams,
-    ) -> Result<BridgeL2TxFeeParams, L1CommunicationError>
-    where
-        P: alloy::providers::Provider<Ethereum>,
-    {
-        let gas_limit = self
+    async fn get_deposit_route(&self) -> Result<DepositRoute, L1CommunicationError> {
+        let base_token = self
             .l2_provider
-            .estimate_gas_l1_to_l2(l1_to_l2_tx)
+            .get_base_token_l1_address()
             .await
-            .map_err(|_| {
-                L1CommunicationError::Custom(
-                    "Error occurred while estimating gas for L1 -> L2 transaction.",
-                )
-            })?;
+            .map_err(L1CommunicationError::rpc("fetching the base token address"))?;
+        let is_eth_based_chain = is_eth_l1_address(base_token);
+        let is_eth_deposit = is_eth_l1_address(self.request.token);
+        let is_base_token_deposit = if is_eth_based_chain {
+            is_eth_deposit
+        } else {
+            self.request.token == base_token
+        };
 
-        let tx_base_cost = bridge_hub_contract
-            .l2TransactionBaseCost(
-                l2_chain_id,
-                U256::from(fee_params.max_fee_per_gas),
-                gas_limit,
-                self.request.gas_per_pubdata_limit,
-            )
-            .call()
-            .await
-            .map_err(|_| {
-                L1CommunicationError::Custom(
-                    "Error occurred while estimating L2 transaction base cost.",
-                )
-            })?;
-        Ok(BridgeL2TxFeeParams {
-            gas_limit,
-            tx_base_cost,
+        let base_token_spender = if is_eth_based_chain {
+            None
+        } else {
+            let bridge_addresses = self
+                .l2_provider
+                .get_bridge_contracts()
+                .await
+                .map_err(L1CommunicationError::rpc("fetching bridge contracts"))?;
+            Some(bridge_addresses.l1_shared_default_bridge.ok_or(
+                L1CommunicationError::MissingBridgeConfiguration(
+                    "L1 shared default bridge is not defined for the chain with a custom base token.",
+                ),
+            )?)
+        };
+
+        Ok(DepositRoute {
+            base_token,
+            is_eth_based_chain,
+            is_base_token_deposit,
+            is_eth_deposit,
+            base_token_spender,
         })
     }
 
@@ -291,42 +279,41 @@ where
         &self,
         sender: Address,
         receiver: Address,
+        route: &DepositRoute,
         bridge_addresses: Option<BridgeAddresses>,
         l2_chain_id: U256,
         fee_params: &FeeParams,
-    ) -> Result<L1TransactionRequest, L1CommunicationError> {
-        let bridge_hub_contract_address = self
-            .l2_provider
-            .get_bridgehub_contract()
-            .await
-            .map_err(|_| {
-                L1CommunicationError::Custom(
-                    "Error occurred while fetching the bridge hub contract address.",
-                )
-            })?
-            .unwrap();
-        let bridge_hub_contract = Bridgehub::new(bridge_hub_contract_address, self.l1_provider);
-
-        let l1_tx_request = if self.request.token == ETHER_L1_ADDRESS {
-            let l2_tx_fee = self
-                .get_bridge_l2_tx_fee_params(
-                    &bridge_hub_contract,
-                    TransactionRequest::default()
-                        .with_from(sender)
-                        .with_to(receiver)
-                        .with_value(self.request.amount)
-                        .with_gas_per_pubdata(self.request.gas_per_pubdata_limit)
-                        .with_input(Bytes::from("0x")),
-                    l2_chain_id,
-                    fee_params,
-                )
-                .await?;
+    ) -> Result<L1DepositTx, L1CommunicationError> {
+        let bridge_hub_contract =
+            get_bridgehub_contract(self.l1_provider, self.l2_provider).await?;
+
+        let (l1_tx_request, l2_tx_fee, mint_value) = if route.is_base_token_deposit {
+            let l2_tx_fee = get_bridge_l2_tx_fee_params(
+                self.l2_provider,
+                &bridge_hub_contract,
+                TransactionRequest::default()
+                    .with_from(sender)
+                    .with_to(receiver)
+                    .with_value(self.request.amount)
+                    .with_gas_per_pubdata(self.request.gas_per_pubdata_limit)
+                    .with_input(Bytes::from("0x")),
+                l2_chain_id,
+                fee_params,
+                self.request.gas_per_pubdata_limit,
+            )
+            .await?;
 
-            let l1_value = l2_tx_fee.tx_base_cost + self.request.amount;
-            bridge_hub_contract
+            let mint_value = l2_tx_fee.tx_base_cost + self.request.amount;
+            // The base token is transferred by the bridge when it's not ETH.
+            let l1_value = if route.is_eth_based_chain {
+                mint_value
+            } else {
+                U256::ZERO
+            };
+            let tx_request = bridge_hub_contract
                 .requestL2TransactionDirect(L2TransactionRequestDirect {
                     chainId: l2_chain_id,
-                    mintValue: l1_value,
+                    mintValue: mint_value,
                     l2Contract: receiver,
                     l2Value: self.request.amount,
                     l2Calldata: Bytes::from_str("0x").unwrap(),
@@ -335,162 +322,189 @@ where
                     factoryDeps: vec![],
                     refundRecipient: sender,
                 })
+                .from(sender)
                 .value(l1_value)
-                .into_transaction_request()
+                .into_transaction_request();
+            (tx_request, l2_tx_fee, mint_value)
         } else {
             let bridge_addresses = bridge_addresses.unwrap();
-            let erc20_contract = ERC20::new(self.request.token, self.l1_provider);
-            let token_data = encode_token_data_for_bridge(&erc20_contract)
-                .await
-                .map_err(|_| {
-                    L1CommunicationError::Custom("Error while encoding ERC20 token data.")
-                })?;
+            let (l1_token, token_data) = if route.is_eth_deposit {
+                (
+                    ETH_ADDRESS_IN_CONTRACTS,
+                    encode_token_data("Ether".to_string(), "ETH".to_string(), 18),
+                )
+            } else {
+                let erc20_contract = ERC20::new(self.request.token, self.l1_provider);
+                let token_data = encode_token_data_for_bridge(&erc20_contract)
+                    .await
+                    .map_err(L1CommunicationError::contract("encoding ERC20 token data"))?;
+                (self.request.token, token_data)
+            };
             let l2_finalize_deposit_calldata = encode_finalize_deposit_calldata(
                 sender,
                 receiver,
-                self.request.token,
+                l1_token,
                 self.request.amount,
                 token_data,
             );
 
-            let l2_tx_fee = self
-                .get_bridge_l2_tx_fee_params(
-                    &bridge_hub_contract,
-                    TransactionRequest::default()
-                        .with_from(apply_l1_to_l2_alias(bridge_addresses.l1_bridge_address))
-                        .with_to(bridge_addresses.l2_bridge_address)
-                        .with_gas_per_pubdata(self.request.gas_per_pubdata_limit)
-                        .with_input(l2_finalize_deposit_calldata),
-                    l2_chain_id,
-                    fee_params,
-                )
-                .await?;
+            let l2_tx_fee = get_bridge_l2_tx_fee_params(
+                self.l2_provider,
+                &bridge_hub_contract,
+                TransactionRequest::default()
+                    .with_from(apply_l1_to_l2_alias(bridge_addresses.l1_bridge_address))
+                    .with_to(bridge_addresses.l2_bridge_address)
+                    .with_gas_per_pubdata(self.request.gas_per_pubdata_limit)
+                    .with_input(l2_finalize_deposit_calldata),
+                l2_chain_id,
+                fee_params,
+                self.request.gas_per_pubdata_limit,
+            )
+            .await?;
 
-            let bridge_calldata =
-                encode_deposit_token_calldata(self.request.token, self.request.amount, receiver);
-            bridge_hub_contract
+            let (bridge_calldata, second_bridge_value) = if route.is_eth_deposit {
+                (
+                    encode_deposit_token_calldata(ETH_ADDRESS_IN_CONTRACTS, U256::ZERO, receiver),
+                    self.request.amount,
+                )
+            } else {
+                (
+                    encode_deposit_token_calldata(
+                        self.request.token,
+                        self.request.amount,
+                        receiver,
+                    ),
+                    U256::ZERO,
+                )
+            };
+            let mint_value = l2_tx_fee.tx_base_cost;
+            let l1_value = if route.is_eth_based_chain {
+                mint_value + second_bridge_value
+            } else {
+                second_bridge_value
+            };
+            let tx_request = bridge_hub_contract
                 .requestL2TransactionTwoBridges(L2TransactionRequestTwoBridges {
                     chainId: l2_chain_id,
-                    mintValue: l2_tx_fee.tx_base_cost,
+                    mintValue: mint_value,
                     l2Value: U256::from(0),
                     l2GasLimit: l2_tx_fee.gas_limit,
                     l2GasPerPubdataByteLimit: self.request.gas_per_pubdata_limit,
                     refundRecipient: sender,
                     secondBridgeAddress: bridge_addresses.l1_bridge_address,
-                    secondBridgeValue: U256::from(0),
+                    secondBridgeValue: second_bridge_value,
                     secondBridgeCalldata: bridge_calldata,
                 })
                 .from(sender)
-                .value(l2_tx_fee.tx_base_cost)
-                .into_transaction_request()
+                .value(l1_value)
+                .into_transaction_request();
+            (tx_request, l2_tx_fee, mint_value)
         };
-        Ok(l1_tx_request
-            .max_fee_per_gas(fee_params.max_fee_per_gas)
-            .max_priority_fee_per_gas(fee_params.max_priority_fee_per_gas))
+        Ok(L1DepositTx {
+            tx_request: l1_tx_request
+                .max_fee_per_gas(fee_params.max_fee_per_gas)
+                .max_priority_fee_per_gas(fee_params.max_priority_fee_per_gas),
+            l2_tx_fee,
+            mint_value,
+        })
     }
 
-    async fn approve_tokens(
+    async fn get_required_approvals(
         &self,
         sender: Address,
+        route: &DepositRoute,
         bridge_addresses: Option<BridgeAddresses>,
-        fee_params: &FeeParams,
-    ) -> Result<(), L1CommunicationError> {
-        if self.request.token == ETHER_L1_ADDRESS {
-            return Ok(());
+        mint_value: U256,
+    ) -> Result<Vec<DepositApproval>, L1CommunicationError> {
+        let mut approvals = vec![];
+        if let Some(base_token_spender) = route.base_token_spender {
+            approvals.extend(
+                get_required_approval(
+                    self.l1_provider,
+                    route.base_token,
+                    sender,
+                    base_token_spender,
+                    mint_value,
+                )
+                .await?,
+            );
         }
-        let bridge_addresses = bridge_addresses.unwrap();
-        let erc20_contract = ERC20::new(self.request.token, self.l1_provider);
-        let token_allowance = erc20_contract
-            .allowance(sender, bridge_addresses.l1_bridge_address)
-            .call()
-            .await
-            .map_err(|_| {
-                L1CommunicationError::Custom(
-                    "Error occurred while fetching token allowance for the bridge.",
+        if !route.is_base_token_deposit && !route.is_eth_deposit {
+            let bridge_addresses = bridge_addresses.unwrap();
+            approvals.extend(
+                get_required_approval(
+                    self.l1_provider,
+                    self.request.token,
+                    sender,
+                    bridge_addresses.l1_bridge_address,
+                    self.request.amount,
                 )
-            })?;
-
-        let allowance_deficit = self.request.amount - token_allowance;
-        if allowance_deficit > U256::from(0) {
-            if !self.request.auto_approval {
-                return Err(L1CommunicationError::Custom(
-                    "Deposit request auto_approval is disabled and the current token allowance won't cover the deposit. Consider enabling deposit request auto_approval or approving tokens manually before the deposit.",
-                ));
-            }
-            let approve_tx_builder = erc20_contract
-                .approve(bridge_addresses.l1_bridge_address, allowance_deficit)
-                .from(sender);
-            let approve_tx = approve_tx_builder.into_transaction_request();
-            let approve_tx_gas_limit = self.get_l1_tx_gas_limit(&approve_tx).await?;
-
-            self
-                .l1_provider
-                .send_transaction( approve_tx
-                .max_fee_per_gas(fee_params.max_fee_per_gas)
-                .max_priority_fee_per_gas(fee_params.max_priority_fee_per_gas)
-                .gas_limit(approve_tx_gas_limit))
-                .await.map_err(|_| {
-                    L1CommunicationError::Custom(
-                        "Error occurred while approving tokens for the bridge address",
-                    )
-                })?
-                .watch()
+                .await?,
+            );
+        }
+        Ok(approvals)
+    }
+
+    async fn check_balance(
+        &self,
+        sender: Address,
+        token: Address,
+        required: U256,
+    ) -> Result<(), L1CommunicationError> {
+        let available = if is_eth_l1_address(token) {
+            self.l1_provider
+                .get_balance(sender)
                 .await
-                .map_err(|_| {
-                    L1CommunicationError::Custom(
-                                "Error occurred while approving tokens for the bridge address. Approve transaction has failed.",
-                )
-                })?;
+                .map_err(L1CommunicationError::rpc("fetching ETH balance"))?
+        } else {
+            ERC20::new(token, self.l1_provider)
+                .balanceOf(sender)
+                .call()
+                .await
+                .map_err(L1CommunicationError::contract("fetching token balance"))?
+        };
+        if available < required {
+            return Err(L1CommunicationError::InsufficientBalance {
+                token,
+                required,
+                available,
+            });
         }
         Ok(())
     }
 
-    async fn submit(
-        &self,
-        tx_request: &L1TransactionRequest,
-    ) -> Result<L1TransactionReceipt, L1CommunicationError> {
-        let l1_gas_limit = self.get_l1_tx_gas_limit(tx_request).await?;
-        let l1_tx_request = tx_request.clone().with_gas_limit(l1_gas_limit);
-        let l1_tx_receipt = self
-            .l1_provider
-            .send_transaction(l1_tx_request)
-            .await
-            .map_err(|_| {
-                L1CommunicationError::Custom(
-                    "Error occurred while sending the L1 -> L2 deposit transaction.",
-                )
-            })?
-            .get_receipt()
-            .await
-            .map_err(|_| {
-                L1CommunicationError::Custom(
-                    "Error occurred while sending the L1 -> L2 deposit transaction receipt.",
-                )
-            })?;
-        Ok(L1TransactionReceipt::new(
-            l1_tx_receipt,
-            self.l2_provider.root().clone(),
-        ))
+    async fn check_balances(&self, prepared: &PreparedDeposit) -> Result<(), L1CommunicationError> {
+        let route = &prepared.route;
+        let l1_value = prepared.l1_deposit_tx.tx_request.value.unwrap_or_default();
+        if !l1_value.is_zero() {
+            self.check_balance(prepared.sender, ETHER_L1_ADDRESS, l1_value)
+                .await?;
+        }
+        if !route.is_eth_based_chain {
+            self.check_balance(
+                prepared.sender,
+                route.base_token,
+                prepared.l1_deposit_tx.mint_value,
+            )
+            .await?;
+        }
+        if !route.is_base_token_deposit && !route.is_eth_deposit {
+            self.check_balance(prepared.sender, self.request.token, self.request.amount)
+                .await?;
+        }
+        Ok(())
     }
 
-    /// Executes specified deposit request. This will handle:
-    /// - Approving tokens if necessary.
-    /// - Sending the deposit transaction.
-    /// - Returning the [`L1TransactionReceipt`] of the deposit transaction.
-    ///
-    /// Returned receipt can be converted into a pending L2 transaction and awaited
-    /// using [`PendingTransactionBuilder`](https://docs.rs/alloy/latest/alloy/providers/struct.PendingTransactionBuilder.html)
-    /// interface.
-    ///
-    /// ## Returns
-    ///
-    /// L1TransactionReceipt of the deposit transaction.
-    pub async fn execute(&self) -> Result<L1TransactionReceipt, L1CommunicationError> {
-        let l2_chain_id = U256::from(self.l2_provider.get_chain_id().await.map_err(|_| {
-            L1CommunicationError::Custom("Error occurred while fetching L2 chain id.")
-        })?);
+    async fn prepare(&self) -> Result<PreparedDeposit, L1CommunicationError> {
+        let l2_chain_id = U256::from(
+            self.l2_provider
+                .get_chain_id()
+                .await
+                .map_err(L1CommunicationError::rpc("fetching L2 chain id"))?,
+        );
 
-        let bridge_addresses = if self.request.token != ETHER_L1_ADDRESS {
+        let route = self.get_deposit_route().await?;
+        let bridge_addresses = if !route.is_base_token_deposit {
             let (l1_bridge_address, l2_bridge_address) =
                 self.get_bridge_addresses_for_deposit(l2_chain_id).await?;
 
@@ -502,24 +516,108 @@ where
             None
         };
 
-        let sender = self.l2_provider.wallet().default_signer_address();
+        // Custom accounts can't send L1 transactions, so the default EOA is used as the sender.
+        let sender = NetworkWallet::<Ethereum>::default_signer_address(self.l2_provider.wallet());
         let receiver = self.request.receiver.unwrap_or(sender);
 
-        let l1_fee_params = self.get_l1_fee_params().await?;
+        let fee_params = get_l1_fee_params(self.l1_provider).await?;
 
         let l1_deposit_tx = self
             .get_l1_deposit_tx(
                 sender,
                 receiver,
+                &route,
                 bridge_addresses,
                 l2_chain_id,
-                &l1_fee_params,
+                &fee_params,
+            )
+            .await?;
+
+        Ok(PreparedDeposit {
+            sender,
+            route,
+            bridge_addresses,
+            fee_params,
+            l1_deposit_tx,
+        })
+    }
+
+    /// Estimates the cost of the deposit request without sending any L1 transaction.
+    /// Performs the same steps as [`DepositExecutor::execute`], but stops before approving
+    /// tokens and submitting the deposit.
+    ///
+    /// ## Returns
+    ///
+    /// DepositQuote with the fee parameters and the required token approvals.
+    pub async fn quote(&self) -> Result<DepositQuote, L1CommunicationError> {
+        let prepared = self.prepare().await?;
+        let approvals = self
+            .get_required_approvals(
+                prepared.sender,
+                &prepared.route,
+                prepared.bridge_addresses,
+                prepared.l1_deposit_tx.mint_value,
             )
             .await?;
+        let l1_tx_request = prepared.l1_deposit_tx.tx_request;
+        let l1_gas_limit = if approvals.is_empty() {
+            Some(get_l1_tx_gas_limit(self.l1_provider, &l1_tx_request).await?)
+        } else {
+            None
+        };
 
-        self.approve_tokens(sender, bridge_addresses, &l1_fee_params)
+        Ok(DepositQuote {
+            l1_gas_limit,
+            max_fee_per_gas: prepared.fee_params.max_fee_per_gas,
+            max_priority_fee_per_gas: prepared.fee_params.max_priority_fee_per_gas,
+            l2_gas_limit: prepared.l1_deposit_tx.l2_tx_fee.gas_limit,
+            l2_tx_base_cost: prepared.l1_deposit_tx.l2_tx_fee.tx_base_cost,
+            mint_value: prepared.l1_deposit_tx.mint_value,
+            l1_value: l1_tx_request.value.unwrap_or_default(),
+            approvals,
+            l1_tx_request,
+        })
+    }
+
+    /// Executes specified deposit request. This will handle:
+    /// - Checking that the sender has enough funds for the deposit.
+    /// - Approving tokens if necessary.
+    /// - Sending the deposit transaction.
+    /// - Returning the [`L1TransactionReceipt`] of the deposit transaction.
+    ///
+    /// Returned receipt can be converted into a pending L2 transaction and awaited
+    /// using [`PendingTransactionBuilder`](https://docs.rs/alloy/latest/alloy/providers/struct.PendingTransactionBuilder.html)
+    /// interface.
+    ///
+    /// ## Returns
+    ///
+    /// L1TransactionReceipt of the deposit transaction.
+    pub async fn execute(&self) -> Result<L1TransactionReceipt, L1CommunicationError> {
+        let prepared = self.prepare().await?;
+        self.check_balances(&prepared).await?;
+        let approvals = self
+            .get_required_approvals(
+                prepared.sender,
+                &prepared.route,
+                prepared.bridge_addresses,
+                prepared.l1_deposit_tx.mint_value,
+            )
             .await?;
 
-        self.submit(&l1_deposit_tx).await
+        approve_tokens(
+            self.l1_provider,
+            prepared.sender,
+            &approvals,
+            self.request.auto_approval,
+            &prepared.fee_params,
+        )
+        .await?;
+
+        submit_l1_to_l2_tx(
+            self.l1_provider,
+            self.l2_provider,
+            &prepared.l1_deposit_tx.tx_request,
+        )
+        .await
     }
 }
@@ -0,0 +1,203 @@
+//! Resolution of the factory dependencies for contract deployments.
+
+use crate::{
+    artifacts::{ArtifactError, Artifacts, ZksolcArtifact},
+    network::{
+        transaction_request::TransactionRequest,
+        unsigned_tx::eip712::{estimate_factory_deps_pubdata, hash_bytecode},
+    },
+    provider::ZksyncProvider,
+};
+use alloy::{
+    network::TransactionBuilder,
+    primitives::{Address, B256, Bytes},
+};
+
+/// Default limit for the total size of factory dependencies sent within a single transaction.
+///
+/// It is chosen conservatively to keep the encoded transaction within the size limits of the node.
+pub const DEFAULT_MAX_FACTORY_DEPS_SIZE: usize = 100_000;
+
+/// Errors that can occur while resolving factory dependencies.
+#[derive(Debug, thiserror::Error)]
+pub enum FactoryDepsError {
+    /// Contract or its factory dependencies cannot be resolved from the artifacts.
+    #[error(transparent)]
+    Artifact(#[from] ArtifactError),
+    /// Failed to check whether the bytecode is known to the network.
+    #[error("Failed to query KnownCodesStorage: {0}")]
+    Contract(#[from] alloy::contract::Error),
+}
+
+/// Transactions required to deploy a contract.
+#[derive(Debug, Clone)]
+pub struct PreparedDeployment {
+    /// Transactions publishing the factory dependencies that didn't fit into the deployment
+    /// transaction. They must be executed before the deployment transaction.
+    pub preparatory: Vec<TransactionRequest>,
+    /// Transaction deploying the contract.
+    pub deployment: TransactionRequest,
+}
+
+impl PreparedDeployment {
+    /// Estimates the amount of pubdata required to publish the factory dependencies
+    /// of all the transactions.
+    ///
+    /// See [`estimate_factory_deps_pubdata`] for details.
+    pub fn estimated_pubdata(&self) -> usize {
+        let factory_deps: Vec<_> = std::iter::once(&self.deployment)
+            .chain(&self.preparatory)
+            .filter_map(TransactionRequest::factory_deps)
+            .flatten()
+            .collect();
+        estimate_factory_deps_pubdata(&factory_deps)
+    }
+}
+
+/// Resolves factory dependencies of the contracts from the artifacts, omitting the bytecodes
+/// that are already known to the network.
+///
+/// Factory dependencies that do not fit into a single transaction are published via preparatory
+/// transactions: empty transfers from the deployer to itself.
+pub struct FactoryDepsResolver<'a, P>
+where
+    P: ZksyncProvider,
+{
+    provider: &'a P,
+    artifacts: &'a Artifacts,
+    max_batch_size: usize,
+}
+
+impl<'a, P> FactoryDepsResolver<'a, P>
+where
+    P: ZksyncProvider,
+{
+    /// Creates a new resolver over the provided artifacts.
+    pub fn new(provider: &'a P, artifacts: &'a Artifacts) -> Self {
+        Self {
+            provider,
+            artifacts,
+            max_batch_size: DEFAULT_MAX_FACTORY_DEPS_SIZE,
+        }
+    }
+
+    /// Sets the limit for the total size of factory dependencies sent within a single transaction.
+    /// Dependencies exceeding the limit on their own are sent in separate transactions.
+    pub fn with_max_batch_size(mut self, max_batch_size: usize) -> Self {
+        self.max_batch_size = max_batch_size;
+        self
+    }
+
+    /// Returns the factory dependencies of the artifact, including the transitive ones,
+    /// that are not known to the network yet.
+    pub async fn unknown_factory_deps(
+        &self,
+        artifact: &ZksolcArtifact,
+    ) -> Result<Vec<Bytes>, FactoryDepsError> {
+        let mut unknown = Vec::new();
+        for dep in self.artifacts.resolve_factory_deps(artifact)? {
+            if !self.is_known(&dep).await? {
+                unknown.push(dep);
+            }
+        }
+        Ok(unknown)
+    }
+
+    /// Prepares transactions deploying the contract with the given name via `create`.
+    ///
+    /// `deployer` is the sender of the transactions.
+    pub async fn create_requests(
+        &self,
+        deployer: Address,
+        name: &str,
+        constructor_data: Vec<u8>,
+    ) -> Result<PreparedDeployment, FactoryDepsError> {
+        let artifact = self
+            .artifacts
+            .get(name)
+            .ok_or_else(|| ArtifactError::ContractNotFound(name.to_owned()))?;
+        let code = (!self.is_known(&artifact.bytecode).await?).then(|| artifact.bytecode.clone());
+        let factory_deps = self.unknown_factory_deps(artifact).await?;
+
+        let mut batches = split_into_batches(code, factory_deps, self.max_batch_size).into_iter();
+        let deployment = TransactionRequest::default()
+            .with_from(deployer)
+            .with_create_params(artifact.bytecode.to_vec(), constructor_data, Vec::new())
+            .map_err(ArtifactError::from)?
+            .with_factory_deps(batches.next().unwrap_or_default());
+        let preparatory = batches
+            .map(|batch| {
+                TransactionRequest::default()
+                    .with_from(deployer)
+                    .with_to(deployer)
+                    .with_factory_deps(batch)
+            })
+            .collect();
+
+        Ok(PreparedDeployment {
+            preparatory,
+            deployment,
+        })
+    }
+
+    async fn is_known(&self, bytecode: &[u8]) -> Result<bool, FactoryDepsError> {
+        let hash = B256::from(hash_bytecode(bytecode).map_err(ArtifactError::from)?);
+        Ok(self.provider.is_bytecode_known(hash).await?)
+    }
+}
+
+/// Splits the factory dependencies into batches not exceeding `max_batch_size` in total.
+/// The first batch always contains the deployed contract `code`, if provided.
+fn split_into_batches(
+    code: Option<Bytes>,
+    factory_deps: Vec<Bytes>,
+    max_batch_size: usize,
+) -> Vec<Vec<Bytes>> {
+    let mut batches: Vec<Vec<Bytes>> = vec![code.into_iter().collect()];
+    let mut batch_size: usize = batches[0].iter().map(|dep| dep.len()).sum();
+    for dep in factory_deps {
+        if batch_size + dep.len() > max_batch_size && !batches.last().unwrap().is_empty() {
+            batches.push(Vec::new());
+            batch_size = 0;
+        }
+        batch_size += dep.len();
+        batches.last_mut().unwrap().push(dep);
+    }
+    batches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dep(byte: u8, len: usize) -> Bytes {
+        vec![byte; len].into()
+    }
+
+    #[test]
+    fn splits_factory_deps_into_batches() {
+        let batches = split_into_batches(
+            Some(dep(0, 64)),
+            vec![dep(1, 32), dep(2, 64), dep(3, 256), dep(4, 32)],
+            128,
+        );
+        assert_eq!(
+            batches,
+            vec![
+                vec![dep(0, 64), dep(1, 32)],
+                vec![dep(2, 64)],
+                vec![dep(3, 256)],
+                vec![dep(4, 32)],
+            ]
+        );
+    }
+
+    #[test]
+    fn known_code_is_not_included() {
+        let batches = split_into_batches(None, vec![dep(1, 32)], 128);
+        assert_eq!(batches, vec![vec![dep(1, 32)]]);
+
+        let batches = split_into_batches(None, vec![], 128);
+        assert_eq!(batches, vec![Vec::<Bytes>::new()]);
+    }
+}
@@ -14,6 +14,10 @@ use crate::network::{Zksync, transaction_request::TransactionRequest};
 
 use super::{Eip712Fee, ZksyncProvider};
 
+pub use self::paymaster::{PaymasterFiller, TestnetPaymasterFiller};
+
+mod paymaster;
+
 /// [Filler](https://docs.rs/alloy/latest/alloy/providers/fillers/trait.TxFiller.html) for EIP-712 transaction type.
 ///
 /// Can fill fields such as `gas_limit`, `max_fee_per_gas`, `max_priority_fee_per_gas`, and `gas_per_pubdata`.
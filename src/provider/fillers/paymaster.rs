@@ -0,0 +1,249 @@
+//! Filler that attaches paymaster parameters to the transactions.
+
+use std::{
+    fmt,
+    sync::{Arc, OnceLock},
+};
+
+use alloy::{
+    network::TransactionBuilder,
+    primitives::{Address, Bytes, U256},
+    providers::{
+        Provider, SendableTx,
+        fillers::{FillerControlFlow, TxFiller},
+    },
+    transports::TransportResult,
+};
+
+use crate::{
+    network::{
+        Zksync,
+        transaction_request::TransactionRequest,
+        unsigned_tx::eip712::{PaymasterFlow, PaymasterParams},
+    },
+    provider::{Eip712Fee, ZksyncProvider},
+};
+
+type TokenPriceFn = dyn Fn(U256) -> U256 + Send + Sync;
+
+/// [Filler](https://docs.rs/alloy/latest/alloy/providers/fillers/trait.TxFiller.html) that attaches
+/// paymaster parameters to the transactions.
+///
+/// Fee is estimated with the paymaster attached, and for the approval-based flow the minimal allowance
+/// is set to cover `max_fee_per_gas * gas_limit`, converted to the token via the token price callback.
+///
+/// Gas limit and fees are always overridden with the values estimated with the paymaster, since the
+/// ones estimated without it (e.g. by [`Eip712FeeFiller`](super::Eip712FeeFiller)) are too low for
+/// the paymaster flows. Thus the filler can be combined with the recommended fillers in any order.
+#[derive(Clone)]
+pub struct PaymasterFiller {
+    paymaster: Address,
+    flow: PaymasterFlow,
+    token_price: Arc<TokenPriceFn>,
+}
+
+impl fmt::Debug for PaymasterFiller {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PaymasterFiller")
+            .field("paymaster", &self.paymaster)
+            .field("flow", &self.flow)
+            .finish_non_exhaustive()
+    }
+}
+
+impl PaymasterFiller {
+    /// Creates a filler for the provided paymaster and flow.
+    /// For the approval-based flow, the minimal allowance of the flow is replaced with the computed one.
+    pub fn new(paymaster: Address, flow: PaymasterFlow) -> Self {
+        Self {
+            paymaster,
+            flow,
+            token_price: Arc::new(|fee| fee),
+        }
+    }
+
+    /// Creates a filler for the general paymaster flow.
+    pub fn general(paymaster: Address, inner_input: Bytes) -> Self {
+        Self::new(paymaster, PaymasterFlow::General { inner_input })
+    }
+
+    /// Creates a filler for the approval-based paymaster flow.
+    pub fn approval_based(paymaster: Address, token: Address, inner_input: Bytes) -> Self {
+        Self::new(
+            paymaster,
+            PaymasterFlow::ApprovalBased {
+                token,
+                min_allowance: U256::ZERO,
+                inner_input,
+            },
+        )
+    }
+
+    /// Sets the callback that converts the fee in the base token into the amount of
+    /// the approved token. By default, the fee is used as is.
+    pub fn with_token_price(
+        mut self,
+        token_price: impl Fn(U256) -> U256 + Send + Sync + 'static,
+    ) -> Self {
+        self.token_price = Arc::new(token_price);
+        self
+    }
+
+    /// Returns paymaster parameters with the provided minimal allowance for the approval-based flow.
+    fn paymaster_params(&self, allowance: U256) -> PaymasterParams {
+        let flow = match &self.flow {
+            PaymasterFlow::ApprovalBased {
+                token, inner_input, ..
+            } => PaymasterFlow::ApprovalBased {
+                token: *token,
+                min_allowance: allowance,
+                inner_input: inner_input.clone(),
+            },
+            flow => flow.clone(),
+        };
+        PaymasterParams::new(self.paymaster, &flow)
+    }
+}
+
+impl TxFiller<Zksync> for PaymasterFiller {
+    type Fillable = (Eip712Fee, PaymasterParams);
+
+    fn status(&self, tx: &TransactionRequest) -> FillerControlFlow {
+        if tx.paymaster_params().is_some() {
+            return FillerControlFlow::Finished;
+        }
+        if tx.from().is_none() {
+            return FillerControlFlow::missing("PaymasterFiller", vec!["from"]);
+        }
+        FillerControlFlow::Ready
+    }
+
+    fn fill_sync(&self, _tx: &mut SendableTx<Zksync>) {}
+
+    async fn prepare<P>(
+        &self,
+        provider: &P,
+        tx: &TransactionRequest,
+    ) -> TransportResult<Self::Fillable>
+    where
+        P: Provider<Zksync>,
+    {
+        // Estimation requires a non-zero allowance for the approval-based flow.
+        let estimation_tx = tx
+            .clone()
+            .with_paymaster_params(self.paymaster_params(U256::from(1)));
+        let fee = provider.estimate_fee(estimation_tx).await?;
+
+        let allowance =
+            (self.token_price)(U256::from(fee.max_fee_per_gas) * U256::from(fee.gas_limit));
+        Ok((fee, self.paymaster_params(allowance)))
+    }
+
+    async fn fill(
+        &self,
+        fillable: Self::Fillable,
+        tx: SendableTx<Zksync>,
+    ) -> TransportResult<SendableTx<Zksync>> {
+        Ok(fill_paymaster(fillable, tx))
+    }
+}
+
+/// Sets the paymaster params along with the fee estimated for them.
+fn fill_paymaster(
+    (fee, paymaster_params): (Eip712Fee, PaymasterParams),
+    mut tx: SendableTx<Zksync>,
+) -> SendableTx<Zksync> {
+    if let Some(builder) = tx.as_mut_builder() {
+        builder.set_paymaster_params(paymaster_params);
+        // Fee fields are overridden, as the allowance of the approval-based flow is computed from them.
+        builder.set_gas_limit(fee.gas_limit);
+        builder.set_max_fee_per_gas(fee.max_fee_per_gas);
+        builder.set_max_priority_fee_per_gas(fee.max_priority_fee_per_gas);
+        // TODO: Should be `is_none()` once `gas_per_pubdata` in TransactionRequest is `Option`
+        if builder.gas_per_pubdata().unwrap_or_default() == U256::ZERO {
+            builder.set_gas_per_pubdata(fee.gas_per_pubdata_limit);
+        }
+    }
+    tx
+}
+
+/// [Filler](https://docs.rs/alloy/latest/alloy/providers/fillers/trait.TxFiller.html) that pays
+/// the transaction fees via the testnet paymaster, if the network advertises one.
+///
+/// Fees are paid in the configured ERC20 token using the approval-based flow; the testnet paymaster
+/// accepts any token at a 1:1 rate. If `zks_getTestnetPaymaster` returns `None`, transactions are
+/// left untouched and fees are paid as usual. The paymaster address is requested once and cached.
+///
+/// Same as [`PaymasterFiller`], it overrides the gas limit and fees with the ones estimated with
+/// the paymaster, e.g. `zksync_provider().with_recommended_fillers().filler(TestnetPaymasterFiller::new(token))`.
+#[derive(Debug, Clone)]
+pub struct TestnetPaymasterFiller {
+    token: Address,
+    paymaster: Arc<OnceLock<Option<Address>>>,
+}
+
+impl TestnetPaymasterFiller {
+    /// Creates a filler that pays fees in the provided ERC20 token.
+    pub fn new(token: Address) -> Self {
+        Self {
+            token,
+            paymaster: Arc::default(),
+        }
+    }
+
+    async fn get_paymaster<P>(&self, provider: &P) -> TransportResult<Option<Address>>
+    where
+        P: Provider<Zksync>,
+    {
+        if let Some(paymaster) = self.paymaster.get() {
+            return Ok(*paymaster);
+        }
+        let paymaster = provider.get_testnet_paymaster().await?;
+        Ok(*self.paymaster.get_or_init(|| paymaster))
+    }
+}
+
+impl TxFiller<Zksync> for TestnetPaymasterFiller {
+    type Fillable = Option<(Eip712Fee, PaymasterParams)>;
+
+    fn status(&self, tx: &TransactionRequest) -> FillerControlFlow {
+        // Nothing to fill if the network is known to have no testnet paymaster.
+        if tx.paymaster_params().is_some() || self.paymaster.get() == Some(&None) {
+            return FillerControlFlow::Finished;
+        }
+        if tx.from().is_none() {
+            return FillerControlFlow::missing("TestnetPaymasterFiller", vec!["from"]);
+        }
+        FillerControlFlow::Ready
+    }
+
+    fn fill_sync(&self, _tx: &mut SendableTx<Zksync>) {}
+
+    async fn prepare<P>(
+        &self,
+        provider: &P,
+        tx: &TransactionRequest,
+    ) -> TransportResult<Self::Fillable>
+    where
+        P: Provider<Zksync>,
+    {
+        let Some(paymaster) = self.get_paymaster(provider).await? else {
+            return Ok(None);
+        };
+        PaymasterFiller::approval_based(paymaster, self.token, Bytes::new())
+            .prepare(provider, tx)
+            .await
+            .map(Some)
+    }
+
+    async fn fill(
+        &self,
+        fillable: Self::Fillable,
+        tx: SendableTx<Zksync>,
+    ) -> TransportResult<SendableTx<Zksync>> {
+        match fillable {
+            Some(fillable) => Ok(fill_paymaster(fillable, tx)),
+            None => Ok(tx),
+        }
+    }
+}
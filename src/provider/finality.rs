@@ -0,0 +1,167 @@
+//! Waiting for the L1 finality of L2 transactions.
+
+use crate::{
+    network::Zksync,
+    provider::{L1CommunicationError, ZksyncProvider, polling::Poller},
+    types::{BlockDetails, BlockStatus, L1BatchDetails, TransactionStatus},
+};
+use alloy::{
+    network::ReceiptResponse as _,
+    primitives::B256,
+    providers::{PendingTransactionBuilder, Provider, RootProvider},
+};
+use std::time::Duration;
+
+/// Finality level of the L2 transaction.
+/// Levels are ordered, so that each level implies all the previous ones.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum FinalityLevel {
+    /// The block with the transaction is sealed on L2.
+    Sealed,
+    /// The batch with the transaction is committed on L1.
+    Committed,
+    /// The batch with the transaction is proven on L1.
+    Proven,
+    /// The batch with the transaction is executed on L1.
+    Executed,
+}
+
+impl From<BlockStatus> for FinalityLevel {
+    fn from(status: BlockStatus) -> Self {
+        match status {
+            BlockStatus::Sealed => Self::Sealed,
+            BlockStatus::Verified => Self::Executed,
+        }
+    }
+}
+
+/// Details of the L2 transaction once it reached the awaited finality level.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FinalityStatus {
+    /// Details of the block with the transaction.
+    pub block: BlockDetails,
+    /// Details of the L1 batch with the transaction.
+    /// Can be None only for [`FinalityLevel::Sealed`], if the batch is not yet sealed.
+    pub l1_batch: Option<L1BatchDetails>,
+}
+
+/// Type that waits until the L2 transaction reaches the requested finality level on L1.
+pub struct FinalityWatcher {
+    l2_provider: RootProvider<Zksync>,
+    l2_tx_hash: B256,
+    level: FinalityLevel,
+    poller: Poller,
+}
+
+impl FinalityWatcher {
+    /// Creates a watcher for the L2 transaction with the provided hash.
+    pub fn new(l2_provider: RootProvider<Zksync>, l2_tx_hash: B256, level: FinalityLevel) -> Self {
+        Self {
+            l2_provider,
+            l2_tx_hash,
+            level,
+            poller: Poller::default(),
+        }
+    }
+
+    /// Sets the interval between the status requests.
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poller.interval = poll_interval;
+        self
+    }
+
+    /// Sets the timeout for waiting. If not set, waiting is not limited in time.
+    pub fn with_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.poller.timeout = timeout;
+        self
+    }
+
+    /// Returns the hash of the watched L2 transaction.
+    pub fn tx_hash(&self) -> B256 {
+        self.l2_tx_hash
+    }
+
+    /// Returns the status of the transaction if it reached the awaited finality level.
+    /// Fails if the transaction failed on L2, since it will never be finalized.
+    async fn check(&self) -> Result<Option<FinalityStatus>, L1CommunicationError> {
+        let tx_details = self
+            .l2_provider
+            .get_transaction_details(self.l2_tx_hash)
+            .await
+            .map_err(L1CommunicationError::rpc(
+                "fetching the transaction details",
+            ))?;
+        match tx_details.map(|details| details.status) {
+            None | Some(TransactionStatus::Pending) => return Ok(None),
+            Some(TransactionStatus::Failed) => {
+                return Err(L1CommunicationError::TransactionFailed(self.l2_tx_hash));
+            }
+            Some(_) => {}
+        }
+
+        let Some(block_number) = self
+            .l2_provider
+            .get_transaction_receipt(self.l2_tx_hash)
+            .await
+            .map_err(L1CommunicationError::rpc(
+                "fetching the transaction receipt",
+            ))?
+            .and_then(|receipt| receipt.block_number())
+        else {
+            return Ok(None);
+        };
+        let Some(block) = self
+            .l2_provider
+            .get_block_details(block_number)
+            .await
+            .map_err(L1CommunicationError::rpc("fetching the block details"))?
+        else {
+            return Ok(None);
+        };
+
+        let l1_batch = self
+            .l2_provider
+            .get_l1_batch_details(block.l1_batch_number)
+            .await
+            .map_err(L1CommunicationError::rpc("fetching the L1 batch details"))?;
+        let reached = match self.level {
+            FinalityLevel::Sealed => true,
+            FinalityLevel::Committed => l1_batch
+                .as_ref()
+                .is_some_and(|batch| batch.commit_tx_hash.is_some()),
+            FinalityLevel::Proven => l1_batch
+                .as_ref()
+                .is_some_and(|batch| batch.prove_tx_hash.is_some()),
+            FinalityLevel::Executed => l1_batch.as_ref().is_some_and(|batch| {
+                batch.execute_tx_hash.is_some()
+                    || FinalityLevel::from(batch.status.clone()) == FinalityLevel::Executed
+            }),
+        };
+        Ok(reached.then_some(FinalityStatus { block, l1_batch }))
+    }
+
+    /// Waits until the transaction reaches the awaited finality level.
+    ///
+    /// ## Returns
+    ///
+    /// FinalityStatus with the block and L1 batch details of the transaction.
+    pub async fn watch(&self) -> Result<FinalityStatus, L1CommunicationError> {
+        self.poller
+            .poll(self.l2_tx_hash, || self.check(), |status| status)
+            .await
+    }
+}
+
+/// Extension trait for [`PendingTransactionBuilder`] that allows waiting for the L1 finality
+/// of the L2 transaction.
+pub trait PendingTransactionFinalityExt {
+    /// Returns a [`FinalityWatcher`] that resolves once the transaction reaches the provided
+    /// finality level.
+    fn with_finality(self, level: FinalityLevel) -> FinalityWatcher;
+}
+
+impl PendingTransactionFinalityExt for PendingTransactionBuilder<Zksync> {
+    fn with_finality(self, level: FinalityLevel) -> FinalityWatcher {
+        FinalityWatcher::new(self.provider().clone(), *self.tx_hash(), level)
+    }
+}
@@ -0,0 +1,316 @@
+//! Implementation of the withdrawal finalization logic.
+
+use crate::{
+    contracts::{
+        l1::{
+            l1_bridge::L1Bridge,
+            l1_nullifier::L1Nullifier::{self, FinalizeL1DepositParams},
+        },
+        l2::l1_messenger::{L1_MESSENGER_ADDRESS, L1Messenger::L1MessageSent},
+    },
+    provider::{L1CommunicationError, ZksyncProvider},
+};
+use alloy::{
+    contract::Error as ContractError,
+    network::Ethereum,
+    primitives::{Address, B256, Bytes, U256},
+    providers::Provider,
+    rpc::types::eth::TransactionReceipt,
+};
+
+/// Parameters required to finalize the withdrawal on L1.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FinalizeWithdrawalParams {
+    /// L1 batch number where the withdrawal was processed.
+    pub l1_batch_number: u64,
+    /// Position of the withdrawal message in the L2 logs Merkle tree.
+    pub l2_message_index: u32,
+    /// Number of the withdrawal transaction in the L1 batch.
+    pub l2_tx_number_in_batch: u16,
+    /// Withdrawal message sent via the L1Messenger.
+    pub message: Bytes,
+    /// Sender of the withdrawal message on L2.
+    pub sender: Address,
+    /// Merkle proof of the inclusion of the withdrawal message.
+    pub proof: Vec<B256>,
+}
+
+/// Finalization status of the withdrawal on L1.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WithdrawalStatus {
+    /// The batch with the withdrawal is not yet executed on L1, so the withdrawal cannot be
+    /// finalized yet.
+    NotReady,
+    /// The batch with the withdrawal is executed on L1, so the withdrawal can be finalized,
+    /// but it was not finalized yet.
+    NotFinalized,
+    /// The withdrawal was already finalized on L1.
+    Finalized,
+}
+
+/// L1 contract that finalizes the withdrawals of the chain.
+enum WithdrawalFinalizer<'a, P> {
+    /// L1 shared bridge, used before protocol version 26.
+    SharedBridge(L1Bridge::L1BridgeInstance<&'a P>),
+    /// L1Nullifier, used since protocol version 26.
+    Nullifier(L1Nullifier::L1NullifierInstance<&'a P>),
+}
+
+/// Returns true if the contract call failed because the contract does not implement the function.
+fn is_missing_function(error: &ContractError) -> bool {
+    match error {
+        ContractError::ZeroData(..) => true,
+        ContractError::TransportError(error) => error
+            .as_error_resp()
+            .is_some_and(|payload| payload.message.contains("revert")),
+        _ => false,
+    }
+}
+
+/// Type that handles withdrawal finalization on L1.
+pub struct FinalizeWithdrawalExecutor<'a, P1, P2>
+where
+    P1: Provider<Ethereum>,
+    P2: ZksyncProvider + ?Sized,
+{
+    l1_provider: &'a P1,
+    l2_provider: &'a P2,
+    l2_tx_hash: B256,
+    index: usize,
+}
+
+impl<'a, P1, P2> FinalizeWithdrawalExecutor<'a, P1, P2>
+where
+    P1: Provider<Ethereum>,
+    P2: ZksyncProvider + ?Sized,
+{
+    /// Prepares an executor for finalizing the first withdrawal of the L2 transaction.
+    pub fn new(l1_provider: &'a P1, l2_provider: &'a P2, l2_tx_hash: B256) -> Self {
+        FinalizeWithdrawalExecutor {
+            l1_provider,
+            l2_provider,
+            l2_tx_hash,
+            index: 0,
+        }
+    }
+
+    /// Sets the index of the withdrawal in the L2 transaction.
+    /// Only needed when a single transaction initiates several withdrawals.
+    pub fn with_index(mut self, index: usize) -> Self {
+        self.index = index;
+        self
+    }
+
+    /// Gathers the parameters required to finalize the withdrawal on L1.
+    ///
+    /// Fails if the L1 batch containing the withdrawal transaction is not yet processed
+    /// and the L2 -> L1 log proof is not available.
+    pub async fn get_finalize_withdrawal_params(
+        &self,
+    ) -> Result<FinalizeWithdrawalParams, L1CommunicationError> {
+        self.find_finalize_withdrawal_params()
+            .await?
+            .ok_or(L1CommunicationError::BatchNotProcessed(self.l2_tx_hash))
+    }
+
+    /// Returns `None` if the withdrawal is not yet included in an L1 batch or its log proof is not available.
+    async fn find_finalize_withdrawal_params(
+        &self,
+    ) -> Result<Option<FinalizeWithdrawalParams>, L1CommunicationError> {
+        let receipt = self
+            .l2_provider
+            .get_transaction_receipt(self.l2_tx_hash)
+            .await
+            .map_err(L1CommunicationError::rpc(
+                "fetching the withdrawal transaction receipt",
+            ))?
+            .ok_or(L1CommunicationError::TransactionNotFound(self.l2_tx_hash))?;
+
+        let l1_message_sent = receipt
+            .logs()
+            .iter()
+            .filter(|log| log.address() == L1_MESSENGER_ADDRESS)
+            .filter_map(|log| log.log_decode::<L1MessageSent>().ok())
+            .nth(self.index)
+            .ok_or(L1CommunicationError::L1MessageSentLogNotFound)?;
+
+        let l2_to_l1_log_index = receipt
+            .l2_to_l1_logs()
+            .iter()
+            .enumerate()
+            .filter(|(_, log)| log.sender == L1_MESSENGER_ADDRESS)
+            .nth(self.index)
+            .map(|(log_index, _)| log_index)
+            .ok_or(L1CommunicationError::L2ToL1LogNotFound(self.l2_tx_hash))?;
+
+        let (Some(l1_batch_number), Some(l1_batch_tx_index)) =
+            (receipt.l1_batch_number(), receipt.l1_batch_tx_index())
+        else {
+            return Ok(None);
+        };
+        let l2_tx_number_in_batch =
+            l1_batch_tx_index
+                .try_into()
+                .map_err(L1CommunicationError::invalid_data(
+                    "converting the withdrawal transaction index in the L1 batch",
+                ))?;
+
+        let Some(log_proof) = self
+            .l2_provider
+            .get_l2_to_l1_log_proof(self.l2_tx_hash, Some(l2_to_l1_log_index))
+            .await
+            .map_err(L1CommunicationError::rpc("fetching the L2 -> L1 log proof"))?
+        else {
+            return Ok(None);
+        };
+
+        Ok(Some(FinalizeWithdrawalParams {
+            l1_batch_number: l1_batch_number.to::<u64>(),
+            l2_message_index: log_proof.id,
+            l2_tx_number_in_batch,
+            message: l1_message_sent.inner.data._message,
+            sender: l1_message_sent.inner.data._sender,
+            proof: log_proof.proof,
+        }))
+    }
+
+    /// Returns the L1Nullifier if the L1 shared bridge of the chain is the L1AssetRouter,
+    /// and the L1 shared bridge itself otherwise.
+    async fn get_withdrawal_finalizer(
+        &self,
+    ) -> Result<WithdrawalFinalizer<'a, P1>, L1CommunicationError> {
+        let bridge_addresses = self
+            .l2_provider
+            .get_bridge_contracts()
+            .await
+            .map_err(L1CommunicationError::rpc("fetching bridge contracts"))?;
+        let l1_bridge_address = bridge_addresses.l1_shared_default_bridge.ok_or(
+            L1CommunicationError::MissingBridgeConfiguration(
+                "L1 shared default bridge is not defined for the chain.",
+            ),
+        )?;
+        let l1_bridge = L1Bridge::new(l1_bridge_address, self.l1_provider);
+        match l1_bridge.L1_NULLIFIER().call().await {
+            Ok(l1_nullifier) => Ok(WithdrawalFinalizer::Nullifier(L1Nullifier::new(
+                l1_nullifier,
+                self.l1_provider,
+            ))),
+            // The legacy L1 shared bridge doesn't expose the nullifier.
+            Err(error) if is_missing_function(&error) => {
+                Ok(WithdrawalFinalizer::SharedBridge(l1_bridge))
+            }
+            Err(error) => Err(L1CommunicationError::contract(
+                "fetching the L1 nullifier address",
+            )(error)),
+        }
+    }
+
+    async fn get_l2_chain_id(&self) -> Result<U256, L1CommunicationError> {
+        let l2_chain_id = self
+            .l2_provider
+            .get_chain_id()
+            .await
+            .map_err(L1CommunicationError::rpc("fetching L2 chain id"))?;
+        Ok(U256::from(l2_chain_id))
+    }
+
+    /// Checks the finalization status of the withdrawal on L1.
+    ///
+    /// ## Returns
+    ///
+    /// [`WithdrawalStatus`] of the withdrawal.
+    pub async fn status(&self) -> Result<WithdrawalStatus, L1CommunicationError> {
+        let Some(params) = self.find_finalize_withdrawal_params().await? else {
+            return Ok(WithdrawalStatus::NotReady);
+        };
+        // The log proof is available once the batch is sealed, but the withdrawal can only be
+        // finalized after the batch is executed on L1.
+        let is_executed = self
+            .l2_provider
+            .get_l1_batch_details(params.l1_batch_number)
+            .await
+            .map_err(L1CommunicationError::rpc("fetching the L1 batch details"))?
+            .is_some_and(|batch| batch.execute_tx_hash.is_some());
+        if !is_executed {
+            return Ok(WithdrawalStatus::NotReady);
+        }
+
+        let l2_chain_id = self.get_l2_chain_id().await?;
+        let l1_batch_number = U256::from(params.l1_batch_number);
+        let l2_message_index = U256::from(params.l2_message_index);
+        let is_finalized = match self.get_withdrawal_finalizer().await? {
+            WithdrawalFinalizer::SharedBridge(l1_bridge) => {
+                l1_bridge
+                    .isWithdrawalFinalized(l2_chain_id, l1_batch_number, l2_message_index)
+                    .call()
+                    .await
+            }
+            WithdrawalFinalizer::Nullifier(l1_nullifier) => {
+                l1_nullifier
+                    .isWithdrawalFinalized(l2_chain_id, l1_batch_number, l2_message_index)
+                    .call()
+                    .await
+            }
+        }
+        .map_err(L1CommunicationError::contract(
+            "checking the withdrawal finalization status",
+        ))?;
+
+        Ok(if is_finalized {
+            WithdrawalStatus::Finalized
+        } else {
+            WithdrawalStatus::NotFinalized
+        })
+    }
+
+    /// Executes the withdrawal finalization. This will handle:
+    /// - Gathering the withdrawal message and its L2 -> L1 log proof.
+    /// - Sending the finalization transaction to the L1Nullifier, or to the L1 shared bridge
+    ///   for chains using protocol versions before 26.
+    ///
+    /// ## Returns
+    ///
+    /// TransactionReceipt of the L1 finalization transaction.
+    pub async fn execute(&self) -> Result<TransactionReceipt, L1CommunicationError> {
+        let params = self.get_finalize_withdrawal_params().await?;
+
+        let l2_chain_id = self.get_l2_chain_id().await?;
+        match self.get_withdrawal_finalizer().await? {
+            WithdrawalFinalizer::SharedBridge(l1_bridge) => {
+                l1_bridge
+                    .finalizeWithdrawal(
+                        l2_chain_id,
+                        U256::from(params.l1_batch_number),
+                        U256::from(params.l2_message_index),
+                        params.l2_tx_number_in_batch,
+                        params.message,
+                        params.proof,
+                    )
+                    .send()
+                    .await
+            }
+            WithdrawalFinalizer::Nullifier(l1_nullifier) => {
+                l1_nullifier
+                    .finalizeDeposit(FinalizeL1DepositParams {
+                        chainId: l2_chain_id,
+                        l2BatchNumber: U256::from(params.l1_batch_number),
+                        l2MessageIndex: U256::from(params.l2_message_index),
+                        l2Sender: params.sender,
+                        l2TxNumberInBatch: params.l2_tx_number_in_batch,
+                        message: params.message,
+                        merkleProof: params.proof,
+                    })
+                    .send()
+                    .await
+            }
+        }
+        .map_err(L1CommunicationError::contract(
+            "sending the L1 withdrawal finalization transaction",
+        ))?
+        .get_receipt()
+        .await
+        .map_err(L1CommunicationError::pending(
+            "getting the L1 withdrawal finalization transaction receipt",
+        ))
+    }
+}
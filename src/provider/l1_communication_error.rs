@@ -1,8 +1,218 @@
-/// Enum to describe errors that might occur during L1 -> L2 communication.
+use alloy::{
+    contract::Error as ContractError,
+    primitives::{Address, B256, Bytes, U256},
+    providers::PendingTransactionError,
+    sol_types::decode_revert_reason,
+    transports::{RpcError, TransportError},
+};
+
+/// Enum to describe errors that might occur during L1 <-> L2 communication.
 #[derive(Debug, thiserror::Error)]
 pub enum L1CommunicationError {
     #[error("NewPriorityRequest event log was not found in L1 -> L2 transaction.")]
     NewPriorityRequestLogNotFound,
-    #[error("Custom L1 -> L2 communication error.")]
-    Custom(&'static str),
+    #[error("L1MessageSent event log was not found in L2 -> L1 transaction.")]
+    L1MessageSentLogNotFound,
+    /// Request to the node failed.
+    #[error("RPC request failed while {context}: {source}")]
+    Rpc {
+        context: &'static str,
+        #[source]
+        source: TransportError,
+    },
+    /// Contract call or transaction reverted.
+    #[error("Contract reverted while {context}: {}", reason.as_deref().unwrap_or("unknown reason"))]
+    ContractRevert {
+        context: &'static str,
+        /// Revert reason decoded from the revert data, if any.
+        reason: Option<String>,
+        /// Raw revert data.
+        data: Bytes,
+    },
+    /// Contract interaction failed for reasons other than a revert, e.g. a malformed response.
+    #[error("Contract interaction failed while {context}: {source}")]
+    Contract {
+        context: &'static str,
+        #[source]
+        source: ContractError,
+    },
+    /// Sent transaction could not be tracked until its inclusion.
+    #[error("Pending transaction failed while {context}: {source}")]
+    PendingTransaction {
+        context: &'static str,
+        #[source]
+        source: PendingTransactionError,
+    },
+    /// Signer could not sign the transaction.
+    #[error("Signer failed while {context}: {source}")]
+    Signer {
+        context: &'static str,
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+    /// Account does not hold enough tokens for the operation.
+    #[error("Insufficient balance of {token}: required {required}, available {available}.")]
+    InsufficientBalance {
+        token: Address,
+        required: U256,
+        available: U256,
+    },
+    /// Token allowance does not cover the operation and automatic approval is disabled.
+    #[error(
+        "Insufficient allowance of {token} for {spender}: required {required}, available {available}."
+    )]
+    InsufficientAllowance {
+        token: Address,
+        spender: Address,
+        required: U256,
+        available: U256,
+    },
+    /// Bridge contracts required for the operation are not configured for the chain.
+    #[error("Missing bridge configuration: {0}")]
+    MissingBridgeConfiguration(&'static str),
+    /// Transaction was not found on L2.
+    #[error("Transaction {0} was not found.")]
+    TransactionNotFound(B256),
+    /// Transaction is not yet included in a processed L1 batch, so its L2 -> L1 log proof is not available.
+    #[error("Transaction {0} is not yet included in a processed L1 batch.")]
+    BatchNotProcessed(B256),
+    /// Expected L2 -> L1 log was not found in the transaction receipt.
+    #[error("L2 -> L1 log was not found in transaction {0}.")]
+    L2ToL1LogNotFound(B256),
+    /// Deposit can't be claimed because it was successfully executed on L2.
+    #[error("Deposit {0} was successfully executed on L2 and can't be claimed.")]
+    DepositNotFailed(B256),
+    /// Transaction failed on L2, so it will never reach the awaited state.
+    #[error("Transaction {0} failed on L2.")]
+    TransactionFailed(B256),
+    /// Transaction did not reach the awaited state within the timeout.
+    #[error("Timed out while waiting for transaction {0}.")]
+    Timeout(B256),
+    /// Data returned by the node does not match the expectations.
+    #[error("Invalid data while {context}: {source}")]
+    InvalidData {
+        context: &'static str,
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+}
+
+impl L1CommunicationError {
+    /// Returns a mapper for the node request errors, distinguishing reverts and signer failures.
+    pub(crate) fn rpc(context: &'static str) -> impl FnOnce(TransportError) -> Self {
+        move |source| Self::from_rpc(context, source)
+    }
+
+    /// Returns a mapper for the contract call errors, distinguishing reverts and signer failures.
+    pub(crate) fn contract(context: &'static str) -> impl FnOnce(ContractError) -> Self {
+        move |source| match source {
+            ContractError::TransportError(source) => Self::from_rpc(context, source),
+            ContractError::PendingTransactionError(source) => Self::pending(context)(source),
+            source => match source.as_revert_data() {
+                Some(data) => Self::revert(context, data),
+                None => Self::Contract { context, source },
+            },
+        }
+    }
+
+    /// Returns a mapper for the errors that occur while awaiting sent transactions.
+    pub(crate) fn pending(context: &'static str) -> impl FnOnce(PendingTransactionError) -> Self {
+        move |source| match source {
+            PendingTransactionError::TransportError(source) => Self::from_rpc(context, source),
+            source => Self::PendingTransaction { context, source },
+        }
+    }
+
+    /// Returns a mapper for the errors caused by malformed data.
+    pub(crate) fn invalid_data<E>(context: &'static str) -> impl FnOnce(E) -> Self
+    where
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        move |source| Self::InvalidData {
+            context,
+            source: Box::new(source),
+        }
+    }
+
+    fn from_rpc(context: &'static str, source: TransportError) -> Self {
+        if let Some(data) = source.as_error_resp().and_then(|e| e.as_revert_data()) {
+            return Self::revert(context, data);
+        }
+        match source {
+            RpcError::LocalUsageError(source) => Self::Signer { context, source },
+            source => Self::Rpc { context, source },
+        }
+    }
+
+    fn revert(context: &'static str, data: Bytes) -> Self {
+        Self::ContractRevert {
+            context,
+            reason: decode_revert_reason(&data),
+            data,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::L1CommunicationError;
+    use alloy::{
+        hex,
+        sol_types::{Revert, SolError},
+        transports::{RpcError, TransportErrorKind},
+    };
+
+    #[test]
+    fn rpc_error_with_revert_data_is_mapped_to_contract_revert() {
+        let revert_data = Revert::from("Insufficient funds").abi_encode();
+        let payload = serde_json::from_value(serde_json::json!({
+            "code": 3,
+            "message": "execution reverted",
+            "data": hex::encode_prefixed(&revert_data),
+        }))
+        .unwrap();
+
+        let error = L1CommunicationError::rpc("estimating gas")(RpcError::ErrorResp(payload));
+
+        match error {
+            L1CommunicationError::ContractRevert {
+                context,
+                reason,
+                data,
+            } => {
+                assert_eq!(context, "estimating gas");
+                assert_eq!(reason.as_deref(), Some("revert: Insufficient funds"));
+                assert_eq!(data.as_ref(), revert_data.as_slice());
+            }
+            error => panic!("unexpected error: {error:?}"),
+        }
+    }
+
+    #[test]
+    fn local_usage_error_is_mapped_to_signer_error() {
+        let error = L1CommunicationError::rpc("sending transaction")(RpcError::local_usage_str(
+            "missing signer",
+        ));
+        assert!(matches!(
+            error,
+            L1CommunicationError::Signer {
+                context: "sending transaction",
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn transport_error_is_mapped_to_rpc_error() {
+        let error = L1CommunicationError::rpc("fetching L2 chain id")(
+            TransportErrorKind::custom_str("connection refused"),
+        );
+        assert!(matches!(
+            error,
+            L1CommunicationError::Rpc {
+                context: "fetching L2 chain id",
+                ..
+            }
+        ));
+    }
 }
@@ -0,0 +1,282 @@
+//! Shared helpers for the flows that send L1 -> L2 transactions.
+
+use crate::{
+    contracts::{common::erc20::ERC20, l1::bridge_hub::Bridgehub},
+    network::{Zksync, transaction_request::TransactionRequest},
+    provider::{
+        DepositApproval, L1CommunicationError, ZksyncProvider,
+        l1_transaction_receipt::L1TransactionReceipt,
+    },
+};
+use alloy::{
+    eips::eip1559::Eip1559Estimation,
+    network::{Ethereum, TransactionBuilder},
+    primitives::{Address, U256},
+    providers::{Provider, utils::Eip1559Estimator},
+    rpc::types::eth::TransactionRequest as L1TransactionRequest,
+};
+
+/// Gas per pubdata limit used for L1 -> L2 transactions by default.
+pub const REQUIRED_L1_TO_L2_GAS_PER_PUBDATA_LIMIT: u64 = 800;
+
+#[derive(Clone, Debug, Copy)]
+pub(crate) struct FeeParams {
+    pub max_fee_per_gas: u128,
+    pub max_priority_fee_per_gas: u128,
+}
+
+#[derive(Clone, Debug, Copy)]
+pub(crate) struct BridgeL2TxFeeParams {
+    pub gas_limit: U256,
+    pub tx_base_cost: U256,
+}
+
+/// Scales the gas limit to ensure the transaction will be accepted.
+// Gas limit scaling logic is taken from the JS SDK:
+// https://github.com/zksync-sdk/zksync-ethers/blob/64763688d1bb5cee4a4c220c3841b803c74b0d05/src/utils.ts#L1451
+pub fn scale_l1_gas_limit(l1_gas_limit: u64) -> u64 {
+    /// Numerator used in scaling the gas limit to ensure acceptance of `L1->L2` transactions.
+    /// This constant is part of a coefficient calculation to adjust the gas limit to account for variations
+    /// in the SDK estimation, ensuring the transaction will be accepted.
+    const L1_FEE_ESTIMATION_COEF_NUMERATOR: u64 = 12;
+
+    /// Denominator used in scaling the gas limit to ensure acceptance of `L1->L2` transactions.
+    /// This constant is part of a coefficient calculation to adjust the gas limit to account for variations
+    /// in the SDK estimation, ensuring the transaction will be accepted.
+    const L1_FEE_ESTIMATION_COEF_DENOMINATOR: u64 = 10;
+    l1_gas_limit * L1_FEE_ESTIMATION_COEF_NUMERATOR / L1_FEE_ESTIMATION_COEF_DENOMINATOR
+}
+
+pub(crate) async fn get_bridgehub_contract<'a, P1, P2>(
+    l1_provider: &'a P1,
+    l2_provider: &P2,
+) -> Result<Bridgehub::BridgehubInstance<&'a P1>, L1CommunicationError>
+where
+    P1: Provider<Ethereum>,
+    P2: ZksyncProvider + ?Sized,
+{
+    let bridge_hub_contract_address = l2_provider
+        .get_bridgehub_contract()
+        .await
+        .map_err(L1CommunicationError::rpc(
+            "fetching the bridge hub contract address",
+        ))?
+        .ok_or(L1CommunicationError::MissingBridgeConfiguration(
+            "Bridge hub contract is not defined for the chain.",
+        ))?;
+    Ok(Bridgehub::new(bridge_hub_contract_address, l1_provider))
+}
+
+pub(crate) async fn get_l1_fee_params<P>(l1_provider: &P) -> Result<FeeParams, L1CommunicationError>
+where
+    P: Provider<Ethereum>,
+{
+    let max_priority_fee_per_gas =
+        l1_provider
+            .get_max_priority_fee_per_gas()
+            .await
+            .map_err(L1CommunicationError::rpc(
+                "fetching L1 max_priority_fee_per_gas",
+            ))?;
+    // fees adjustment is taken from the JS SDK:
+    // https://github.com/zksync-sdk/zksync-ethers/blob/64763688d1bb5cee4a4c220c3841b803c74b0d05/src/adapters.ts#L2069
+    let base_l1_fees_data = l1_provider
+        .estimate_eip1559_fees_with(Eip1559Estimator::new(|base_fee_per_gas, _| {
+            Eip1559Estimation {
+                max_fee_per_gas: base_fee_per_gas * 3 / 2,
+                max_priority_fee_per_gas: 0,
+            }
+        }))
+        .await
+        .map_err(L1CommunicationError::rpc("estimating L1 base fees"))?;
+    let max_fee_per_gas = base_l1_fees_data.max_fee_per_gas + max_priority_fee_per_gas;
+
+    Ok(FeeParams {
+        max_fee_per_gas,
+        max_priority_fee_per_gas,
+    })
+}
+
+pub(crate) async fn get_l1_tx_gas_limit<P>(
+    l1_provider: &P,
+    tx_request: &L1TransactionRequest,
+) -> Result<u64, L1CommunicationError>
+where
+    P: Provider<Ethereum>,
+{
+    let l1_tx_gas_estimation =
+        l1_provider
+            .estimate_gas(tx_request.clone())
+            .await
+            .map_err(L1CommunicationError::rpc(
+                "estimating gas limit for the L1 transaction",
+            ))?;
+    let l1_gas_limit = scale_l1_gas_limit(l1_tx_gas_estimation);
+    Ok(l1_gas_limit)
+}
+
+pub(crate) async fn get_l2_tx_base_cost<P>(
+    bridge_hub_contract: &Bridgehub::BridgehubInstance<&P>,
+    l2_chain_id: U256,
+    fee_params: &FeeParams,
+    l2_gas_limit: U256,
+    gas_per_pubdata_limit: U256,
+) -> Result<U256, L1CommunicationError>
+where
+    P: Provider<Ethereum>,
+{
+    bridge_hub_contract
+        .l2TransactionBaseCost(
+            l2_chain_id,
+            U256::from(fee_params.max_fee_per_gas),
+            l2_gas_limit,
+            gas_per_pubdata_limit,
+        )
+        .call()
+        .await
+        .map_err(L1CommunicationError::contract(
+            "estimating L2 transaction base cost",
+        ))
+}
+
+pub(crate) async fn get_bridge_l2_tx_fee_params<P1, P2>(
+    l2_provider: &P2,
+    bridge_hub_contract: &Bridgehub::BridgehubInstance<&P1>,
+    l1_to_l2_tx: TransactionRequest,
+    l2_chain_id: U256,
+    fee_params: &FeeParams,
+    gas_per_pubdata_limit: U256,
+) -> Result<BridgeL2TxFeeParams, L1CommunicationError>
+where
+    P1: Provider<Ethereum>,
+    P2: ZksyncProvider + ?Sized,
+{
+    let gas_limit = l2_provider
+        .estimate_gas_l1_to_l2(l1_to_l2_tx)
+        .await
+        .map_err(L1CommunicationError::rpc(
+            "estimating gas for L1 -> L2 transaction",
+        ))?;
+
+    let tx_base_cost = get_l2_tx_base_cost(
+        bridge_hub_contract,
+        l2_chain_id,
+        fee_params,
+        gas_limit,
+        gas_per_pubdata_limit,
+    )
+    .await?;
+    Ok(BridgeL2TxFeeParams {
+        gas_limit,
+        tx_base_cost,
+    })
+}
+
+pub(crate) async fn submit_l1_to_l2_tx<P1, P2>(
+    l1_provider: &P1,
+    l2_provider: &P2,
+    tx_request: &L1TransactionRequest,
+) -> Result<L1TransactionReceipt, L1CommunicationError>
+where
+    P1: Provider<Ethereum>,
+    P2: Provider<Zksync> + ?Sized,
+{
+    let l1_gas_limit = get_l1_tx_gas_limit(l1_provider, tx_request).await?;
+    let l1_tx_request = tx_request.clone().with_gas_limit(l1_gas_limit);
+    let l1_tx_receipt = l1_provider
+        .send_transaction(l1_tx_request)
+        .await
+        .map_err(L1CommunicationError::rpc(
+            "sending the L1 -> L2 transaction",
+        ))?
+        .get_receipt()
+        .await
+        .map_err(L1CommunicationError::pending(
+            "getting the L1 -> L2 transaction receipt",
+        ))?;
+    Ok(L1TransactionReceipt::new(
+        l1_tx_receipt,
+        l2_provider.root().clone(),
+    ))
+}
+
+/// Returns the approval required for `spender` to transfer `amount` of `token` owned by `owner`,
+/// or None if the current allowance is sufficient.
+pub(crate) async fn get_required_approval<P>(
+    l1_provider: &P,
+    token: Address,
+    owner: Address,
+    spender: Address,
+    amount: U256,
+) -> Result<Option<DepositApproval>, L1CommunicationError>
+where
+    P: Provider<Ethereum>,
+{
+    let erc20_contract = ERC20::new(token, l1_provider);
+    let token_allowance = erc20_contract
+        .allowance(owner, spender)
+        .call()
+        .await
+        .map_err(L1CommunicationError::contract(
+            "fetching token allowance for the bridge",
+        ))?;
+
+    if token_allowance >= amount {
+        return Ok(None);
+    }
+    Ok(Some(DepositApproval {
+        token,
+        spender,
+        amount,
+        allowance: token_allowance,
+    }))
+}
+
+/// Sends the approval transactions and waits for their inclusion.
+/// If `auto_approval` is disabled, fails on the first required approval instead.
+pub(crate) async fn approve_tokens<P>(
+    l1_provider: &P,
+    sender: Address,
+    approvals: &[DepositApproval],
+    auto_approval: bool,
+    fee_params: &FeeParams,
+) -> Result<(), L1CommunicationError>
+where
+    P: Provider<Ethereum>,
+{
+    // Approvals are not sent when auto_approval is disabled, so tokens have to be approved manually.
+    if let Some(approval) = approvals.first().filter(|_| !auto_approval) {
+        return Err(L1CommunicationError::InsufficientAllowance {
+            token: approval.token,
+            spender: approval.spender,
+            required: approval.amount,
+            available: approval.allowance,
+        });
+    }
+    for approval in approvals {
+        let erc20_contract = ERC20::new(approval.token, l1_provider);
+        let approve_tx = erc20_contract
+            .approve(approval.spender, approval.amount)
+            .from(sender)
+            .into_transaction_request();
+        let approve_tx_gas_limit = get_l1_tx_gas_limit(l1_provider, &approve_tx).await?;
+
+        l1_provider
+            .send_transaction(
+                approve_tx
+                    .max_fee_per_gas(fee_params.max_fee_per_gas)
+                    .max_priority_fee_per_gas(fee_params.max_priority_fee_per_gas)
+                    .gas_limit(approve_tx_gas_limit),
+            )
+            .await
+            .map_err(L1CommunicationError::rpc(
+                "approving tokens for the bridge address",
+            ))?
+            .watch()
+            .await
+            .map_err(L1CommunicationError::pending(
+                "awaiting the token approval transaction",
+            ))?;
+    }
+    Ok(())
+}
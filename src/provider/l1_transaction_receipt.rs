@@ -1,7 +1,14 @@
-use super::l1_communication_error::L1CommunicationError;
-use crate::{contracts::l1::bridge_hub::Bridgehub::NewPriorityRequest, network::Zksync};
+use super::{
+    claim_failed_deposit::ClaimFailedDepositExecutor, l1_communication_error::L1CommunicationError,
+    priority_op_tracker::PriorityOpTracker,
+};
+use crate::{
+    contracts::l1::bridge_hub::{Bridgehub::NewPriorityRequest, PriorityOp},
+    network::Zksync,
+};
 use alloy::{
-    providers::{PendingTransactionBuilder, RootProvider},
+    network::Ethereum,
+    providers::{PendingTransactionBuilder, Provider, RootProvider},
     rpc::types::eth::TransactionReceipt,
 };
 
@@ -50,4 +57,40 @@ impl L1TransactionReceipt {
             l2_tx_hash,
         ))
     }
+
+    /// Returns the priority operation requested by the L1 transaction.
+    ///
+    /// Will return an error if the transaction does not correspond to an L1->L2 transaction.
+    pub fn get_priority_op(&self) -> Result<PriorityOp, L1CommunicationError> {
+        self.inner
+            .inner
+            .logs()
+            .iter()
+            .find_map(|log| PriorityOp::decode_log(&log.inner).ok())
+            .ok_or(L1CommunicationError::NewPriorityRequestLogNotFound)
+    }
+
+    /// Returns a [`PriorityOpTracker`] that follows the L2 transaction through L2 inclusion,
+    /// batch commit, proof and execution on L1.
+    ///
+    /// Will return an error if the transaction does not correspond to an L1->L2 transaction.
+    pub fn track_priority_op(&self) -> Result<PriorityOpTracker, L1CommunicationError> {
+        let l2_tx_hash = *self.get_l2_tx()?.tx_hash();
+        Ok(PriorityOpTracker::new(self.l2_provider.clone(), l2_tx_hash))
+    }
+
+    /// Claims the funds back on L1 if the L2 part of the deposit failed.
+    /// The L1 batch containing the L2 transaction has to be executed on L1 first.
+    pub async fn claim_failed_deposit<P>(
+        &self,
+        l1_provider: &P,
+    ) -> Result<TransactionReceipt, L1CommunicationError>
+    where
+        P: Provider<Ethereum>,
+    {
+        let l2_tx_hash = *self.get_l2_tx()?.tx_hash();
+        ClaimFailedDepositExecutor::new(l1_provider, &self.l2_provider, l2_tx_hash)
+            .execute()
+            .await
+    }
 }
@@ -0,0 +1,58 @@
+use super::{
+    finalize_withdrawal::FinalizeWithdrawalExecutor, l1_communication_error::L1CommunicationError,
+};
+use crate::network::{Zksync, receipt_response::ReceiptResponse};
+use alloy::{
+    network::{Ethereum, ReceiptResponse as _},
+    primitives::B256,
+    providers::{Provider, RootProvider},
+    rpc::types::eth::TransactionReceipt,
+};
+
+/// A wrapper struct to hold L2 transaction receipt and L2 provider
+/// which is used by the associated functions.
+pub struct L2TransactionReceipt {
+    /// ZKsync transaction receipt.
+    inner: ReceiptResponse,
+    /// A reference to the L2 provider.
+    l2_provider: RootProvider<Zksync>,
+}
+
+impl L2TransactionReceipt {
+    /// Creates a new `L2TransactionReceipt` object.
+    pub fn new(tx_receipt: ReceiptResponse, l2_provider: RootProvider<Zksync>) -> Self {
+        Self {
+            inner: tx_receipt,
+            l2_provider,
+        }
+    }
+
+    /// Returns a receipt for the L2 operation.
+    pub fn get_receipt(&self) -> &ReceiptResponse {
+        &self.inner
+    }
+
+    /// Returns the hash of the L2 transaction.
+    pub fn tx_hash(&self) -> B256 {
+        self.inner.transaction_hash()
+    }
+
+    /// Returns the L2 provider used to create the receipt.
+    pub fn l2_provider(&self) -> &RootProvider<Zksync> {
+        &self.l2_provider
+    }
+
+    /// Finalizes the withdrawal initiated by this L2 transaction on L1.
+    /// The L1 batch containing the transaction has to be executed on L1 first.
+    pub async fn finalize_withdrawal<P>(
+        &self,
+        l1_provider: &P,
+    ) -> Result<TransactionReceipt, L1CommunicationError>
+    where
+        P: Provider<Ethereum>,
+    {
+        FinalizeWithdrawalExecutor::new(l1_provider, &self.l2_provider, self.tx_hash())
+            .execute()
+            .await
+    }
+}
@@ -1,17 +1,35 @@
 //! ZKsync provider.
 
 pub use self::{
-    deposit::{DepositExecutor, DepositRequest},
+    claim_failed_deposit::{ClaimFailedDepositExecutor, ClaimFailedDepositParams},
+    contract_factory::{ContractFactory, DeployError},
+    deposit::{DepositApproval, DepositExecutor, DepositQuote, DepositRequest},
+    factory_deps::{
+        DEFAULT_MAX_FACTORY_DEPS_SIZE, FactoryDepsError, FactoryDepsResolver, PreparedDeployment,
+    },
+    finality::{FinalityLevel, FinalityStatus, FinalityWatcher, PendingTransactionFinalityExt},
+    finalize_withdrawal::{FinalizeWithdrawalExecutor, FinalizeWithdrawalParams, WithdrawalStatus},
     l1_communication_error::L1CommunicationError,
     l1_transaction_receipt::L1TransactionReceipt,
+    l2_transaction_receipt::L2TransactionReceipt,
+    priority_op_tracker::{PriorityOpStage, PriorityOpStatus, PriorityOpTracker},
     provider_builder_ext::ProviderBuilderExt,
+    request_execute::{RequestExecuteExecutor, RequestExecuteRequest},
+    withdraw::{WithdrawExecutor, WithdrawRequest},
 };
 use crate::{
-    network::{Zksync, transaction_request::TransactionRequest},
+    contracts::l2::{
+        known_codes_storage::{KNOWN_CODES_STORAGE_ADDRESS, KnownCodesStorage},
+        nonce_holder::{NONCE_HOLDER_ADDRESS, NonceHolder},
+    },
+    network::{
+        Zksync, transaction_request::TransactionRequest,
+        unsigned_tx::eip712::compute_create_address,
+    },
     types::*,
 };
 use alloy::{
-    network::Ethereum,
+    network::{Ethereum, NetworkWallet},
     primitives::{Address, B256, Bytes, U64, U256},
     providers::{
         Identity, Provider, ProviderBuilder, ProviderCall, WalletProvider,
@@ -23,12 +41,23 @@ use alloy::{
 use fillers::Eip712FeeFiller;
 use std::collections::HashMap;
 
+mod claim_failed_deposit;
+mod contract_factory;
 mod deposit;
+mod factory_deps;
 pub mod fillers;
+mod finality;
+mod finalize_withdrawal;
 mod l1_communication_error;
+mod l1_to_l2;
 mod l1_transaction_receipt;
+mod l2_transaction_receipt;
 pub mod layers;
+mod polling;
+mod priority_op_tracker;
 mod provider_builder_ext;
+mod request_execute;
+mod withdraw;
 
 type GetMsgProofRequest = (u64, Address, B256, Option<usize>);
 
@@ -261,6 +290,72 @@ where
             .request("zks_getProof", (address, keys, l1_batch_number))
             .into()
     }
+
+    /// Checks whether the withdrawal was finalized on L1.
+    ///
+    /// ## Parameters
+    ///
+    /// - `l2_tx_hash`: hash of the L2 withdrawal transaction.
+    /// - `index`: Optional: index of the withdrawal in the transaction, defaults to the first one.
+    /// - `l1_provider`: reference to the L1 provider.
+    ///
+    /// ## Returns
+    ///
+    /// [`WithdrawalStatus`] of the withdrawal.
+    async fn is_withdrawal_finalized<P>(
+        &self,
+        l2_tx_hash: B256,
+        index: Option<usize>,
+        l1_provider: &P,
+    ) -> Result<WithdrawalStatus, L1CommunicationError>
+    where
+        Self: Sized,
+        P: alloy::providers::Provider<Ethereum>,
+    {
+        FinalizeWithdrawalExecutor::new(l1_provider, self, l2_tx_hash)
+            .with_index(index.unwrap_or_default())
+            .status()
+            .await
+    }
+
+    /// Retrieves the deployment nonce of the account from the `NonceHolder` system contract.
+    ///
+    /// Unlike Ethereum, ZKsync uses the deployment nonce rather than the transaction nonce
+    /// to derive the addresses of contracts deployed via `create`.
+    async fn get_deployment_nonce(&self, address: Address) -> Result<U256, alloy::contract::Error>
+    where
+        Self: Sized,
+    {
+        NonceHolder::new(NONCE_HOLDER_ADDRESS, self)
+            .getDeploymentNonce(address)
+            .call()
+            .await
+    }
+
+    /// Checks whether the bytecode with the given hash is known to the network, i.e. whether
+    /// it can be deployed without providing it as a factory dependency.
+    async fn is_bytecode_known(&self, bytecode_hash: B256) -> Result<bool, alloy::contract::Error>
+    where
+        Self: Sized,
+    {
+        let marker = KnownCodesStorage::new(KNOWN_CODES_STORAGE_ADDRESS, self)
+            .getMarker(bytecode_hash)
+            .call()
+            .await?;
+        Ok(!marker.is_zero())
+    }
+
+    /// Predicts the address of the next contract deployed by the account via `create`.
+    async fn predict_create_address(
+        &self,
+        deployer: Address,
+    ) -> Result<Address, alloy::contract::Error>
+    where
+        Self: Sized,
+    {
+        let deployment_nonce = self.get_deployment_nonce(deployer).await?;
+        Ok(compute_create_address(deployer, deployment_nonce))
+    }
 }
 
 /// Trait for ZKsync provider with populated wallet
@@ -289,10 +384,102 @@ pub trait ZksyncProviderWithWallet<T = BoxTransport>:
     ) -> Result<L1TransactionReceipt, L1CommunicationError>
     where
         P: alloy::providers::Provider<Ethereum>,
+        Self::Wallet: NetworkWallet<Ethereum>,
     {
         let deposit_executor = DepositExecutor::new(l1_provider, self, deposit_request);
         deposit_executor.execute().await
     }
+
+    /// Executes an arbitrary L2 call from L1 via the bridgehub.
+    ///
+    /// ## Parameters
+    ///
+    /// - `request`: request which contains the L2 call params including contract address, calldata etc.
+    /// - `l1_provider`: reference to the L1 provider.
+    ///
+    /// ## Returns
+    ///
+    /// L1TransactionReceipt.
+    /// Hint: use returned L1 transaction receipt to get corresponding L2 transaction and wait for its receipt.
+    async fn request_execute<P>(
+        &self,
+        request: &RequestExecuteRequest,
+        l1_provider: &P,
+    ) -> Result<L1TransactionReceipt, L1CommunicationError>
+    where
+        P: alloy::providers::Provider<Ethereum>,
+        Self::Wallet: NetworkWallet<Ethereum>,
+    {
+        let request_execute_executor = RequestExecuteExecutor::new(l1_provider, self, request);
+        request_execute_executor.execute().await
+    }
+
+    /// Withdraws specified L2 token to the L1 address.
+    ///
+    /// ## Parameters
+    ///
+    /// - `withdraw_request`: withdrawal request which contains withdrawal params including amount, token to withdraw etc.
+    ///
+    /// ## Returns
+    ///
+    /// L2TransactionReceipt.
+    /// Hint: the withdrawal has to be finalized on L1 once the L1 batch containing the returned transaction is executed.
+    async fn withdraw(
+        &self,
+        withdraw_request: &WithdrawRequest,
+    ) -> Result<L2TransactionReceipt, L1CommunicationError> {
+        let withdraw_executor = WithdrawExecutor::new(self, withdraw_request);
+        withdraw_executor.execute().await
+    }
+
+    /// Finalizes the withdrawal on L1.
+    ///
+    /// On chains using protocol version 26 or later, the withdrawal is finalized via the
+    /// L1Nullifier, otherwise via the legacy L1 shared bridge.
+    ///
+    /// ## Parameters
+    ///
+    /// - `l2_tx_hash`: hash of the L2 withdrawal transaction.
+    /// - `l1_provider`: reference to the L1 provider.
+    ///
+    /// ## Returns
+    ///
+    /// TransactionReceipt of the L1 finalization transaction.
+    /// Hint: the L1 batch containing the withdrawal has to be executed on L1 before it can be finalized.
+    async fn finalize_withdrawal<P>(
+        &self,
+        l2_tx_hash: B256,
+        l1_provider: &P,
+    ) -> Result<alloy::rpc::types::eth::TransactionReceipt, L1CommunicationError>
+    where
+        P: alloy::providers::Provider<Ethereum>,
+    {
+        let finalize_executor = FinalizeWithdrawalExecutor::new(l1_provider, self, l2_tx_hash);
+        finalize_executor.execute().await
+    }
+
+    /// Claims the funds of a deposit that failed on L2 back to the deposit sender on L1.
+    ///
+    /// ## Parameters
+    ///
+    /// - `l2_tx_hash`: hash of the failed L2 deposit transaction.
+    /// - `l1_provider`: reference to the L1 provider.
+    ///
+    /// ## Returns
+    ///
+    /// TransactionReceipt of the L1 claim transaction.
+    /// Hint: the L1 batch containing the deposit has to be executed on L1 before it can be claimed.
+    async fn claim_failed_deposit<P>(
+        &self,
+        l2_tx_hash: B256,
+        l1_provider: &P,
+    ) -> Result<alloy::rpc::types::eth::TransactionReceipt, L1CommunicationError>
+    where
+        P: alloy::providers::Provider<Ethereum>,
+    {
+        let claim_executor = ClaimFailedDepositExecutor::new(l1_provider, self, l2_tx_hash);
+        claim_executor.execute().await
+    }
 }
 
 impl<P> ZksyncProviderWithWallet for P where P: WalletProvider<Zksync> + Provider<Zksync> {}
@@ -327,7 +514,10 @@ mod tests {
     use std::net::SocketAddr;
 
     use crate::network::unsigned_tx::eip712::PaymasterParams;
+    use crate::wallet::ZksyncWallet;
     use alloy::network::TransactionBuilder;
+    use alloy::providers::fillers::WalletFiller;
+    use alloy::signers::local::PrivateKeySigner;
     use chrono::{DateTime, Utc};
     use jsonrpsee::core::RpcResult;
     use jsonrpsee::server::{RpcModule, Server};
@@ -343,12 +533,18 @@ mod tests {
         RootProvider<Zksync>,
         Zksync,
     >;
-    async fn run_server_and_test<Fut>(
+    type ZKsyncTestWalletProvider = FillProvider<
+        JoinFill<
+            JoinFill<Identity, JoinFill<Eip712FeeFiller, JoinFill<NonceFiller, ChainIdFiller>>>,
+            WalletFiller<ZksyncWallet>,
+        >,
+        RootProvider<Zksync>,
+        Zksync,
+    >;
+
+    async fn start_server(
         register_rpc_module_fn: impl FnOnce(&mut RpcModule<()>),
-        test_fn: impl FnOnce(ZKsyncTestProvider) -> Fut,
-    ) where
-        Fut: Future<Output = ()>,
-    {
+    ) -> alloy::transports::http::reqwest::Url {
         let server = Server::builder()
             .build("127.0.0.1:0".parse::<SocketAddr>().unwrap())
             .await
@@ -358,15 +554,57 @@ mod tests {
 
         let server_addr: SocketAddr = server.local_addr().unwrap();
         let handle = server.start(module);
-        let full_addr = format!("http://{server_addr}");
         tokio::spawn(handle.stopped());
+        format!("http://{server_addr}").parse().unwrap()
+    }
 
+    async fn run_server_and_test<Fut>(
+        register_rpc_module_fn: impl FnOnce(&mut RpcModule<()>),
+        test_fn: impl FnOnce(ZKsyncTestProvider) -> Fut,
+    ) where
+        Fut: Future<Output = ()>,
+    {
         let provider = zksync_provider()
             .with_recommended_fillers()
-            .connect_http(full_addr.parse().unwrap());
+            .connect_http(start_server(register_rpc_module_fn).await);
         test_fn(provider).await;
     }
 
+    fn zksync_wallet_provider(
+        url: alloy::transports::http::reqwest::Url,
+    ) -> ZKsyncTestWalletProvider {
+        zksync_provider()
+            .with_recommended_fillers()
+            .wallet(ZksyncWallet::from(PrivateKeySigner::random()))
+            .connect_http(url)
+    }
+
+    async fn run_server_and_test_with_wallet<Fut>(
+        register_rpc_module_fn: impl FnOnce(&mut RpcModule<()>),
+        test_fn: impl FnOnce(ZKsyncTestWalletProvider) -> Fut,
+    ) where
+        Fut: Future<Output = ()>,
+    {
+        test_fn(zksync_wallet_provider(
+            start_server(register_rpc_module_fn).await,
+        ))
+        .await;
+    }
+
+    /// Runs the test against the mocked L1 and L2 nodes, with the L2 provider having a random wallet.
+    async fn run_l1_and_l2_servers_and_test<T, Fut>(
+        register_l1_rpc_module_fn: impl FnOnce(&mut RpcModule<()>),
+        register_l2_rpc_module_fn: impl FnOnce(&mut RpcModule<()>),
+        test_fn: impl FnOnce(RootProvider<Ethereum>, ZKsyncTestWalletProvider) -> Fut,
+    ) -> T
+    where
+        Fut: Future<Output = T>,
+    {
+        let l1_provider = RootProvider::new_http(start_server(register_l1_rpc_module_fn).await);
+        let l2_provider = zksync_wallet_provider(start_server(register_l2_rpc_module_fn).await);
+        test_fn(l1_provider, l2_provider).await
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn get_main_contract_test() {
         let network_main_contract_address = address!("32400084c286cf3e17e7b677ea9583e60a000324");
@@ -510,6 +748,223 @@ mod tests {
         .await;
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn paymaster_filler_sizes_allowance() {
+        use crate::network::unsigned_tx::eip712::PaymasterFlow;
+        use crate::provider::fillers::PaymasterFiller;
+        use alloy::providers::fillers::TxFiller;
+
+        let paymaster = address!("3333333333333333333333333333333333333333");
+        let token = address!("4444444444444444444444444444444444444444");
+        let network_fee = Eip712Fee {
+            gas_limit: 40000,
+            gas_per_pubdata_limit: U256::from(800),
+            max_fee_per_gas: 60000,
+            max_priority_fee_per_gas: 0,
+        };
+        let network_fee_rpc_response = network_fee.clone();
+
+        let tx_request = TransactionRequest::default()
+            .with_to(address!("1111111111111111111111111111111111111111"))
+            .with_from(address!("2222222222222222222222222222222222222222"));
+
+        run_server_and_test(
+            move |module| {
+                module
+                    .register_method::<RpcResult<Eip712Fee>, _>(
+                        "zks_estimateFee",
+                        move |params, _, _| {
+                            let (tx_request_param,) =
+                                params.parse::<(TransactionRequest,)>().unwrap();
+                            // Fee has to be estimated with the paymaster attached.
+                            let paymaster_params = tx_request_param.paymaster_params().unwrap();
+                            assert_eq!(paymaster_params.paymaster, paymaster);
+                            Ok(network_fee_rpc_response.clone())
+                        },
+                    )
+                    .unwrap();
+            },
+            |provider: ZKsyncTestProvider| async move {
+                let filler = PaymasterFiller::approval_based(paymaster, token, Bytes::new())
+                    .with_token_price(|fee| fee * U256::from(2));
+                let (fee, paymaster_params) = filler.prepare(&provider, &tx_request).await.unwrap();
+                assert_eq!(fee, network_fee);
+                assert_eq!(paymaster_params.paymaster, paymaster);
+                assert_eq!(
+                    paymaster_params.flow().unwrap(),
+                    PaymasterFlow::ApprovalBased {
+                        token,
+                        min_allowance: U256::from(40000u64 * 60000 * 2),
+                        inner_input: Bytes::new(),
+                    }
+                );
+            },
+        )
+        .await;
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn paymaster_filler_overrides_fee_after_recommended_fillers() {
+        use crate::network::unsigned_tx::eip712::PaymasterFlow;
+        use crate::provider::fillers::PaymasterFiller;
+
+        let paymaster = address!("3333333333333333333333333333333333333333");
+        let token = address!("4444444444444444444444444444444444444444");
+        let tx_request = TransactionRequest::default()
+            .with_to(address!("1111111111111111111111111111111111111111"))
+            .with_from(address!("2222222222222222222222222222222222222222"));
+
+        let url = start_server(move |module| {
+            module
+                .register_method::<RpcResult<U64>, _>("eth_chainId", move |_, _, _| {
+                    Ok(U64::from(324))
+                })
+                .unwrap();
+            module
+                .register_method::<RpcResult<U64>, _>("eth_getTransactionCount", move |_, _, _| {
+                    Ok(U64::from(7))
+                })
+                .unwrap();
+            module
+                .register_method::<RpcResult<Eip712Fee>, _>(
+                    "zks_estimateFee",
+                    move |params, _, _| {
+                        let (tx_request_param,) = params.parse::<(TransactionRequest,)>().unwrap();
+                        // Paymaster flows require more gas than the regular transactions.
+                        let gas_limit = match tx_request_param.paymaster_params() {
+                            Some(_) => 90000,
+                            None => 40000,
+                        };
+                        Ok(Eip712Fee {
+                            gas_limit,
+                            gas_per_pubdata_limit: U256::from(800),
+                            max_fee_per_gas: 60000,
+                            max_priority_fee_per_gas: 0,
+                        })
+                    },
+                )
+                .unwrap();
+        })
+        .await;
+
+        // The paymaster filler goes after the fee filler, so it runs on the transaction
+        // that already has the fee estimated without the paymaster.
+        let provider = zksync_provider()
+            .with_recommended_fillers()
+            .filler(PaymasterFiller::approval_based(
+                paymaster,
+                token,
+                Bytes::new(),
+            ))
+            .connect_http(url);
+        let filled = provider.fill(tx_request).await.unwrap();
+        let filled = filled.as_builder().unwrap();
+
+        assert_eq!(filled.gas_limit(), Some(90000));
+        assert_eq!(filled.max_fee_per_gas(), Some(60000));
+        assert_eq!(filled.max_priority_fee_per_gas(), Some(0));
+        let paymaster_params = filled.paymaster_params().unwrap();
+        assert_eq!(paymaster_params.paymaster, paymaster);
+        assert_eq!(
+            paymaster_params.flow().unwrap(),
+            PaymasterFlow::ApprovalBased {
+                token,
+                min_allowance: U256::from(90000u64 * 60000),
+                inner_input: Bytes::new(),
+            }
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn testnet_paymaster_filler_falls_back_when_not_advertised() {
+        use crate::provider::fillers::TestnetPaymasterFiller;
+        use alloy::providers::fillers::TxFiller;
+
+        let tx_request = TransactionRequest::default()
+            .with_to(address!("1111111111111111111111111111111111111111"))
+            .with_from(address!("2222222222222222222222222222222222222222"));
+
+        run_server_and_test(
+            |module| {
+                module
+                    .register_method::<RpcResult<Option<Address>>, _>(
+                        "zks_getTestnetPaymaster",
+                        move |_, _, _| Ok(None),
+                    )
+                    .unwrap();
+            },
+            |provider: ZKsyncTestProvider| async move {
+                let filler = TestnetPaymasterFiller::new(address!(
+                    "4444444444444444444444444444444444444444"
+                ));
+                assert!(filler.status(&tx_request).is_ready());
+                let fillable = filler.prepare(&provider, &tx_request).await.unwrap();
+                assert_eq!(fillable, None);
+                // Network is known to have no paymaster, so the filler shouldn't be invoked again.
+                assert!(filler.status(&tx_request).is_finished());
+            },
+        )
+        .await;
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn testnet_paymaster_filler_uses_advertised_paymaster() {
+        use crate::network::unsigned_tx::eip712::PaymasterFlow;
+        use crate::provider::fillers::TestnetPaymasterFiller;
+        use alloy::providers::fillers::TxFiller;
+
+        let paymaster = address!("3333333333333333333333333333333333333333");
+        let token = address!("4444444444444444444444444444444444444444");
+        let network_fee = Eip712Fee {
+            gas_limit: 40000,
+            gas_per_pubdata_limit: U256::from(800),
+            max_fee_per_gas: 60000,
+            max_priority_fee_per_gas: 0,
+        };
+        let network_fee_rpc_response = network_fee.clone();
+
+        let tx_request = TransactionRequest::default()
+            .with_to(address!("1111111111111111111111111111111111111111"))
+            .with_from(address!("2222222222222222222222222222222222222222"));
+
+        run_server_and_test(
+            move |module| {
+                module
+                    .register_method::<RpcResult<Option<Address>>, _>(
+                        "zks_getTestnetPaymaster",
+                        move |_, _, _| Ok(Some(paymaster)),
+                    )
+                    .unwrap();
+                module
+                    .register_method::<RpcResult<Eip712Fee>, _>(
+                        "zks_estimateFee",
+                        move |_, _, _| Ok(network_fee_rpc_response.clone()),
+                    )
+                    .unwrap();
+            },
+            |provider: ZKsyncTestProvider| async move {
+                let filler = TestnetPaymasterFiller::new(token);
+                let (fee, paymaster_params) = filler
+                    .prepare(&provider, &tx_request)
+                    .await
+                    .unwrap()
+                    .unwrap();
+                assert_eq!(fee, network_fee);
+                assert_eq!(paymaster_params.paymaster, paymaster);
+                // Testnet paymaster accepts tokens at a 1:1 rate.
+                assert_eq!(
+                    paymaster_params.flow().unwrap(),
+                    PaymasterFlow::ApprovalBased {
+                        token,
+                        min_allowance: U256::from(40000u64 * 60000),
+                        inner_input: Bytes::new(),
+                    }
+                );
+            },
+        )
+        .await;
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn estimate_gas_l1_to_l2_test() {
         let network_gas_estimation = U256::from(6789);
@@ -1021,6 +1476,72 @@ mod tests {
         .await;
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn priority_op_tracker_waits_for_stage() {
+        let tx_hash =
+            B256::from_str("0xf1adac176fc939313eea4b72055db0622a10bbd9b7a83097286e84e471d2e7df")
+                .unwrap();
+        let eth_commit_tx_hash =
+            B256::from_str("0x8b65c0cf1012ea9f393197eb24619fd814379b298b238285649e14f936a5eb12")
+                .unwrap();
+        let requests_count = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let requests_count_rpc = requests_count.clone();
+        run_server_and_test(
+            |module| {
+                module
+                    .register_method::<RpcResult<Option<TransactionDetails>>, _>(
+                        "zks_getTransactionDetails",
+                        move |_, _, _| {
+                            // The operation is not known on the first request and is committed afterwards.
+                            let request_idx = requests_count_rpc
+                                .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                            Ok((request_idx > 0).then(|| TransactionDetails {
+                                is_l1_originated: true,
+                                status: TransactionStatus::Included,
+                                fee: U256::from(10000),
+                                gas_per_pubdata: U256::from(800),
+                                initiator_address: address!(
+                                    "3333333333333333333333333333333333333333"
+                                ),
+                                received_at: str_to_utc("2023-03-03T23:52:24.169Z"),
+                                eth_commit_tx_hash: Some(eth_commit_tx_hash),
+                                eth_prove_tx_hash: None,
+                                eth_execute_tx_hash: None,
+                            }))
+                        },
+                    )
+                    .unwrap();
+            },
+            |provider: ZKsyncTestProvider| async move {
+                let tracker = PriorityOpTracker::new(provider.root().clone(), tx_hash)
+                    .with_poll_interval(std::time::Duration::from_millis(10))
+                    .with_timeout(Some(std::time::Duration::from_millis(500)));
+
+                let mut updates = vec![];
+                let status = tracker
+                    .wait_for_with(PriorityOpStage::Committed, |status| {
+                        updates.push(status.stage)
+                    })
+                    .await
+                    .unwrap();
+                assert_eq!(status.stage, PriorityOpStage::Committed);
+                assert_eq!(status.eth_commit_tx_hash, Some(eth_commit_tx_hash));
+                assert!(!status.failed);
+                assert_eq!(
+                    updates,
+                    vec![PriorityOpStage::Pending, PriorityOpStage::Committed]
+                );
+
+                let error = tracker
+                    .wait_for(PriorityOpStage::Executed)
+                    .await
+                    .unwrap_err();
+                assert!(matches!(error, L1CommunicationError::Timeout(hash) if hash == tx_hash));
+            },
+        )
+        .await;
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn get_transaction_details_when_do_not_exist() {
         let tx_hash =
@@ -1234,10 +1755,82 @@ mod tests {
     }
 
     #[tokio::test(flavor = "multi_thread")]
-    async fn get_l1_batch_details_when_exist() {
-        let batch_number = 6578_u64;
-        let network_batch_details = Some(L1BatchDetails {
-            number: 468355,
+    async fn get_raw_block_transactions_parses_protocol_upgrade() {
+        let block_number = 10_u64;
+        run_server_and_test(
+            |module| {
+                module
+                    .register_method::<RpcResult<serde_json::Value>, _>(
+                        "zks_getRawBlockTransactions",
+                        move |_, _, _| {
+                            // Response of the node for a block with a protocol upgrade transaction.
+                            Ok(serde_json::json!([{
+                                "common_data": {
+                                    "ProtocolUpgrade": {
+                                        "sender": "0x0000000000000000000000000000000000008007",
+                                        "upgradeId": 25,
+                                        "maxFeePerGas": "0xee6b280",
+                                        "gasLimit": "0x4c4b400",
+                                        "gasPerPubdataLimit": "0x320",
+                                        "ethBlock": 6734871,
+                                        "canonicalTxHash": "0x8b65c0cf1012ea9f393197eb24619fd814379b298b238285649e14f936a5eb12",
+                                        "toMint": "0x0",
+                                        "refundRecipient": "0x0000000000000000000000000000000000008007"
+                                    }
+                                },
+                                "execute": {
+                                    "contractAddress": "0x0000000000000000000000000000000000008006",
+                                    "calldata": "0xe9f18c17",
+                                    "value": "0x0",
+                                    "factoryDeps": []
+                                },
+                                "received_timestamp_ms": 1727779200000_u64,
+                                "raw_bytes": null
+                            }]))
+                        },
+                    )
+                    .unwrap();
+            },
+            |provider: ZKsyncTestProvider| async move {
+                let txs = provider
+                    .get_raw_block_transactions(block_number)
+                    .await
+                    .unwrap();
+                assert_eq!(txs.len(), 1);
+                let ExecuteTransactionCommon::ProtocolUpgrade(data) = &txs[0].common_data else {
+                    panic!("expected protocol upgrade transaction");
+                };
+                assert_eq!(
+                    data,
+                    &ProtocolUpgradeTxCommonData {
+                        sender: address!("0000000000000000000000000000000000008007"),
+                        upgrade_id: "25".to_string(),
+                        max_fee_per_gas: U256::from(250000000),
+                        gas_limit: U256::from(80000000),
+                        gas_per_pubdata_limit: U256::from(800),
+                        eth_block: 6734871,
+                        canonical_tx_hash: B256::from_str(
+                            "0x8b65c0cf1012ea9f393197eb24619fd814379b298b238285649e14f936a5eb12",
+                        )
+                        .unwrap(),
+                        to_mint: U256::ZERO,
+                        refund_recipient: address!("0000000000000000000000000000000000008007"),
+                    }
+                );
+                assert_eq!(
+                    txs[0].execute.contract_address,
+                    Some(address!("0000000000000000000000000000000000008006"))
+                );
+            },
+        )
+        .await;
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn get_l1_batch_details_when_exist() {
+        let batch_number = 6578_u64;
+        let network_batch_details = Some(L1BatchDetails {
+            number: 468355,
             timestamp: 1711649164,
             l1_tx_count: 1,
             l2_tx_count: 2363,
@@ -1394,6 +1987,107 @@ mod tests {
         .await;
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn get_deployment_nonce_test() {
+        let deployer = address!("36615Cf349d7F6344891B1e7CA7C72883F5dc049");
+        let deployment_nonce = U256::from(5);
+        run_server_and_test(
+            |module| {
+                module
+                    .register_method::<RpcResult<Bytes>, _>("eth_call", move |params, _, _| {
+                        let (request, _) = params
+                            .parse::<(TransactionRequest, serde_json::Value)>()
+                            .unwrap();
+                        assert_eq!(request.to(), Some(NONCE_HOLDER_ADDRESS));
+                        Ok(deployment_nonce.to_be_bytes::<32>().into())
+                    })
+                    .unwrap();
+            },
+            |provider: ZKsyncTestProvider| async move {
+                assert_eq!(
+                    provider.get_deployment_nonce(deployer).await.unwrap(),
+                    deployment_nonce
+                );
+                assert_eq!(
+                    provider.predict_create_address(deployer).await.unwrap(),
+                    compute_create_address(deployer, deployment_nonce)
+                );
+            },
+        )
+        .await;
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn factory_deps_resolver_skips_known_bytecodes() {
+        use crate::artifacts::{Artifacts, ZksolcArtifact};
+        use crate::contracts::l2::known_codes_storage::KnownCodesStorage::getMarkerCall;
+        use crate::network::unsigned_tx::eip712::hash_bytecode;
+        use alloy::sol_types::SolCall;
+
+        let artifact = |byte: u8, deps: &[u8]| ZksolcArtifact {
+            contract_name: Some(format!("Contract{byte}")),
+            source_name: None,
+            abi: Default::default(),
+            bytecode: vec![byte; 32].into(),
+            factory_dependencies: deps
+                .iter()
+                .map(|dep| (hash_bytecode(&[*dep; 32]).unwrap().into(), String::new()))
+                .collect(),
+        };
+        let mut artifacts = Artifacts::new();
+        artifacts.insert(artifact(1, &[2, 3, 4]));
+        artifacts.insert(artifact(2, &[]));
+        artifacts.insert(artifact(3, &[]));
+        artifacts.insert(artifact(4, &[]));
+        let known_hash = B256::from(hash_bytecode(&[3; 32]).unwrap());
+        let deployer = address!("36615Cf349d7F6344891B1e7CA7C72883F5dc049");
+
+        run_server_and_test(
+            |module| {
+                module
+                    .register_method::<RpcResult<Bytes>, _>("eth_call", move |params, _, _| {
+                        let (request, _) = params
+                            .parse::<(TransactionRequest, serde_json::Value)>()
+                            .unwrap();
+                        assert_eq!(request.to(), Some(KNOWN_CODES_STORAGE_ADDRESS));
+                        let call = getMarkerCall::abi_decode(request.input().unwrap()).unwrap();
+                        let marker = U256::from((call._hash == known_hash) as u8);
+                        Ok(marker.to_be_bytes::<32>().into())
+                    })
+                    .unwrap();
+            },
+            |provider: ZKsyncTestProvider| async move {
+                assert!(provider.is_bytecode_known(known_hash).await.unwrap());
+
+                let deployment = FactoryDepsResolver::new(&provider, &artifacts)
+                    .with_max_batch_size(64)
+                    .create_requests(deployer, "Contract1", Vec::new())
+                    .await
+                    .unwrap();
+                assert_eq!(
+                    deployment.deployment.factory_deps().unwrap(),
+                    &vec![Bytes::from(vec![1; 32]), Bytes::from(vec![4; 32])]
+                );
+                assert_eq!(deployment.preparatory.len(), 1);
+                let preparatory = &deployment.preparatory[0];
+                assert_eq!(preparatory.to(), Some(deployer));
+                assert_eq!(
+                    preparatory.factory_deps().unwrap(),
+                    &vec![Bytes::from(vec![2; 32])]
+                );
+                assert_eq!(
+                    deployment.estimated_pubdata(),
+                    crate::network::unsigned_tx::eip712::estimate_factory_deps_pubdata(&[
+                        vec![1; 32],
+                        vec![2; 32],
+                        vec![4; 32],
+                    ])
+                );
+            },
+        )
+        .await;
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn get_l1_batch_block_range_when_exists() {
         let l1_batch_number = 123_u64;
@@ -1582,6 +2276,42 @@ mod tests {
         .await;
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn evm_deployment_requires_evm_emulator() {
+        let protocol_version = ProtocolVersion {
+            minor_version: Some(26),
+            timestamp: 456778_u64,
+            verification_keys_hashes: None,
+            base_system_contracts: Some(BaseSystemContractsHashes {
+                bootloader: B256::repeat_byte(1),
+                default_aa: B256::repeat_byte(2),
+                evm_emulator: None,
+            }),
+            bootloader_code_hash: None,
+            default_account_code_hash: None,
+            evm_emulator_code_hash: None,
+            l2_system_upgrade_tx_hash: None,
+        };
+        assert!(!protocol_version.is_evm_emulator_enabled());
+        run_server_and_test(
+            |module| {
+                module
+                    .register_method::<RpcResult<Option<ProtocolVersion>>, _>(
+                        "zks_getProtocolVersion",
+                        move |_, _, _| Ok(Some(protocol_version.clone())),
+                    )
+                    .unwrap();
+            },
+            |provider: ZKsyncTestProvider| async move {
+                let result = ContractFactory::evm(vec![0x60, 0x80])
+                    .deploy_address(&provider)
+                    .await;
+                assert!(matches!(result, Err(DeployError::EvmEmulatorDisabled)));
+            },
+        )
+        .await;
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn get_protocol_version_when_not_available() {
         let protocol_id = Some(123_u16);
@@ -1695,4 +2425,1030 @@ mod tests {
         )
         .await;
     }
+
+    const L1_SHARED_BRIDGE: Address = address!("1111111111111111111111111111111111111111");
+    const L2_SHARED_BRIDGE: Address = address!("2222222222222222222222222222222222222222");
+
+    fn shared_bridge_addresses() -> BridgeAddresses {
+        BridgeAddresses {
+            l1_shared_default_bridge: Some(L1_SHARED_BRIDGE),
+            l2_shared_default_bridge: Some(L2_SHARED_BRIDGE),
+            l1_erc20_default_bridge: None,
+            l2_erc20_default_bridge: None,
+            l1_weth_bridge: None,
+            l2_weth_bridge: None,
+            l2_legacy_shared_bridge: None,
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn withdraw_tx_depends_on_token() {
+        use crate::contracts::l2::{
+            l2_base_token::{L2_BASE_TOKEN_ADDRESS, L2BaseToken},
+            l2_bridge::L2Bridge,
+        };
+        use alloy::sol_types::SolCall;
+
+        let sender = address!("3333333333333333333333333333333333333333");
+        let receiver = address!("4444444444444444444444444444444444444444");
+        let token = address!("5555555555555555555555555555555555555555");
+        let amount = U256::from(1000);
+        run_server_and_test_with_wallet(
+            |module| {
+                module
+                    .register_method::<RpcResult<BridgeAddresses>, _>(
+                        "zks_getBridgeContracts",
+                        move |_, _, _| Ok(shared_bridge_addresses()),
+                    )
+                    .unwrap();
+            },
+            |provider: ZKsyncTestWalletProvider| async move {
+                // Base token is withdrawn via the L2BaseToken system contract, passing the amount as value.
+                let request = WithdrawRequest::new(amount);
+                let tx = WithdrawExecutor::new(&provider, &request)
+                    .get_l2_withdraw_tx(sender, receiver)
+                    .await
+                    .unwrap();
+                assert_eq!(tx.to(), Some(L2_BASE_TOKEN_ADDRESS));
+                assert_eq!(tx.value(), Some(amount));
+                let call = L2BaseToken::withdrawCall::abi_decode(tx.input().unwrap()).unwrap();
+                assert_eq!(call._l1Receiver, receiver);
+
+                // ERC20 tokens are withdrawn via the L2 shared bridge.
+                let request = WithdrawRequest::new(amount).with_token(token);
+                let tx = WithdrawExecutor::new(&provider, &request)
+                    .get_l2_withdraw_tx(sender, receiver)
+                    .await
+                    .unwrap();
+                assert_eq!(tx.to(), Some(L2_SHARED_BRIDGE));
+                assert_eq!(tx.value(), None);
+                let call = L2Bridge::withdrawCall::abi_decode(tx.input().unwrap()).unwrap();
+                assert_eq!(call._l1Receiver, receiver);
+                assert_eq!(call._l2Token, token);
+                assert_eq!(call._amount, amount);
+            },
+        )
+        .await;
+    }
+
+    const WITHDRAWAL_TX_HASH: B256 = alloy::primitives::b256!(
+        "b2adc4d2b3203e186001dc37fdf02cc8e772518425d263adc6a17dbddff3bfda"
+    );
+    const L1_BATCH_NUMBER: u64 = 0x72ae1;
+
+    /// Receipt of the L2 transaction in the JSON-RPC format, included in [`L1_BATCH_NUMBER`]
+    /// if `in_batch` is set.
+    fn l2_receipt_json(
+        tx_hash: B256,
+        status: bool,
+        in_batch: bool,
+        logs: Vec<alloy::primitives::Log>,
+        l2_to_l1_logs: Vec<(Address, B256)>,
+    ) -> serde_json::Value {
+        let block_hash = B256::repeat_byte(0x50);
+        let l1_batch_number = in_batch.then(|| U64::from(L1_BATCH_NUMBER));
+        let logs: Vec<_> = logs
+            .into_iter()
+            .enumerate()
+            .map(|(log_index, log)| {
+                serde_json::json!({
+                    "address": log.address,
+                    "topics": log.data.topics(),
+                    "data": log.data.data,
+                    "blockHash": block_hash,
+                    "blockNumber": "0x1d1551e",
+                    "l1BatchNumber": l1_batch_number,
+                    "logIndex": U64::from(log_index),
+                    "removed": false,
+                    "transactionHash": tx_hash,
+                    "transactionIndex": "0x0",
+                })
+            })
+            .collect();
+        let l2_to_l1_logs: Vec<_> = l2_to_l1_logs
+            .into_iter()
+            .enumerate()
+            .map(|(log_index, (sender, key))| {
+                serde_json::json!({
+                    "blockHash": block_hash,
+                    "blockNumber": "0x1d1551e",
+                    "isService": true,
+                    "key": key,
+                    "l1BatchNumber": l1_batch_number,
+                    "logIndex": U64::from(log_index),
+                    "sender": sender,
+                    "shardId": "0x0",
+                    "transactionHash": tx_hash,
+                    "transactionIndex": "0x0",
+                    "transactionLogIndex": U64::from(log_index),
+                    "txIndexInL1Batch": in_batch.then_some("0x12d"),
+                    "value": B256::ZERO,
+                })
+            })
+            .collect();
+        serde_json::json!({
+            "blockHash": block_hash,
+            "blockNumber": "0x1d1551e",
+            "contractAddress": null,
+            "cumulativeGasUsed": "0x0",
+            "effectiveGasPrice": "0x17d7840",
+            "from": "0x1bc3366b3664c01b8687b1efcfc6478d9351a8a9",
+            "gasUsed": "0x2b9bcb",
+            "l1BatchNumber": l1_batch_number,
+            "l1BatchTxIndex": in_batch.then_some("0x12d"),
+            "l2ToL1Logs": l2_to_l1_logs,
+            "logs": logs,
+            "logsBloom": alloy::primitives::Bloom::ZERO,
+            "status": if status { "0x1" } else { "0x0" },
+            "to": "0x000000000000000000000000000000000000800a",
+            "transactionHash": tx_hash,
+            "transactionIndex": "0x0",
+            "type": "0x2",
+        })
+    }
+
+    /// Receipt of the base token withdrawal initiated by `sender`.
+    fn withdrawal_receipt_json(
+        sender: Address,
+        message: &Bytes,
+        in_batch: bool,
+    ) -> serde_json::Value {
+        use crate::contracts::l2::l1_messenger::{
+            L1_MESSENGER_ADDRESS, L1Messenger::L1MessageSent,
+        };
+
+        let event = L1MessageSent {
+            _sender: sender,
+            _hash: alloy::primitives::keccak256(message),
+            _message: message.clone(),
+        };
+        l2_receipt_json(
+            WITHDRAWAL_TX_HASH,
+            true,
+            in_batch,
+            vec![alloy::primitives::Log {
+                address: L1_MESSENGER_ADDRESS,
+                data: alloy::primitives::LogData::from(&event),
+            }],
+            vec![(
+                L1_MESSENGER_ADDRESS,
+                B256::left_padding_from(sender.as_slice()),
+            )],
+        )
+    }
+
+    fn l2_to_l1_log_proof() -> L2ToL1LogProof {
+        L2ToL1LogProof {
+            proof: vec![B256::repeat_byte(0x2a), B256::repeat_byte(0x01)],
+            id: 3000,
+            root: B256::repeat_byte(0x8b),
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn finalize_withdrawal_params_are_gathered_from_receipt_and_proof() {
+        let sender = address!("000000000000000000000000000000000000800a");
+        let message = Bytes::from(vec![0x6c, 0x09, 0x60, 0xf9, 1, 2, 3]);
+        let receipt = withdrawal_receipt_json(sender, &message, true);
+        run_l1_and_l2_servers_and_test(
+            |_| {},
+            |module| {
+                module
+                    .register_method::<RpcResult<serde_json::Value>, _>(
+                        "eth_getTransactionReceipt",
+                        move |params, _, _| {
+                            let (tx_hash,) = params.parse::<(B256,)>().unwrap();
+                            assert_eq!(tx_hash, WITHDRAWAL_TX_HASH);
+                            Ok(receipt.clone())
+                        },
+                    )
+                    .unwrap();
+                module
+                    .register_method::<RpcResult<Option<L2ToL1LogProof>>, _>(
+                        "zks_getL2ToL1LogProof",
+                        move |params, _, _| {
+                            let (tx_hash, index) = params.parse::<(B256, Option<usize>)>().unwrap();
+                            assert_eq!(tx_hash, WITHDRAWAL_TX_HASH);
+                            assert_eq!(index, Some(0));
+                            Ok(Some(l2_to_l1_log_proof()))
+                        },
+                    )
+                    .unwrap();
+            },
+            |l1_provider, l2_provider| async move {
+                let params =
+                    FinalizeWithdrawalExecutor::new(&l1_provider, &l2_provider, WITHDRAWAL_TX_HASH)
+                        .get_finalize_withdrawal_params()
+                        .await
+                        .unwrap();
+                assert_eq!(
+                    params,
+                    FinalizeWithdrawalParams {
+                        l1_batch_number: L1_BATCH_NUMBER,
+                        l2_message_index: 3000,
+                        l2_tx_number_in_batch: 0x12d,
+                        message,
+                        sender,
+                        proof: l2_to_l1_log_proof().proof,
+                    }
+                );
+
+                // The transaction initiates a single withdrawal.
+                let error =
+                    FinalizeWithdrawalExecutor::new(&l1_provider, &l2_provider, WITHDRAWAL_TX_HASH)
+                        .with_index(1)
+                        .get_finalize_withdrawal_params()
+                        .await
+                        .unwrap_err();
+                assert!(matches!(
+                    error,
+                    L1CommunicationError::L1MessageSentLogNotFound
+                ));
+            },
+        )
+        .await;
+    }
+
+    /// Details of the [`L1_BATCH_NUMBER`] batch, committed on L1 and optionally executed.
+    fn l1_batch_details(executed: bool) -> L1BatchDetails {
+        L1BatchDetails {
+            number: L1_BATCH_NUMBER,
+            timestamp: 1711649164,
+            l1_tx_count: 1,
+            l2_tx_count: 2363,
+            root_hash: Some(B256::repeat_byte(0x7b)),
+            status: if executed {
+                BlockStatus::Verified
+            } else {
+                BlockStatus::Sealed
+            },
+            commit_tx_hash: Some(B256::repeat_byte(0x5b)),
+            committed_at: Some(str_to_utc("2024-03-28T18:24:49.713730Z")),
+            prove_tx_hash: None,
+            proven_at: None,
+            execute_tx_hash: executed.then(|| B256::repeat_byte(0xbe)),
+            executed_at: executed.then(|| str_to_utc("2024-03-29T18:18:04.204270Z")),
+            l1_gas_price: U256::from(47875552051_u64),
+            l2_fair_gas_price: U256::from(25000000),
+            fair_pubdata_price: Some(U256::from(725000000)),
+            base_system_contracts_hashes: BaseSystemContractsHashes {
+                bootloader: B256::repeat_byte(1),
+                default_aa: B256::repeat_byte(2),
+                evm_emulator: None,
+            },
+        }
+    }
+
+    const L1_NULLIFIER: Address = address!("4444444444444444444444444444444444444444");
+
+    /// Returns the status of the withdrawal depending on the progress of its batch on L1.
+    ///
+    /// The L1 shared bridge exposes the L1 nullifier if `with_nullifier` is set, as since
+    /// protocol version 26.
+    async fn withdrawal_status(
+        in_batch: bool,
+        executed: bool,
+        finalized: bool,
+        with_nullifier: bool,
+    ) -> WithdrawalStatus {
+        use crate::contracts::l1::l1_bridge::L1Bridge::{
+            L1_NULLIFIERCall, isWithdrawalFinalizedCall,
+        };
+        use alloy::sol_types::SolCall;
+        use jsonrpsee::types::ErrorObjectOwned;
+
+        let sender = address!("000000000000000000000000000000000000800a");
+        let receipt = withdrawal_receipt_json(sender, &Bytes::from(vec![1, 2, 3]), in_batch);
+        run_l1_and_l2_servers_and_test(
+            move |module| {
+                module
+                    .register_method::<RpcResult<Bytes>, _>("eth_call", move |params, _, _| {
+                        let (request, _) = params
+                            .parse::<(alloy::rpc::types::TransactionRequest, serde_json::Value)>()
+                            .unwrap();
+                        let input = request.input.input().unwrap();
+                        if input.starts_with(&L1_NULLIFIERCall::SELECTOR) {
+                            assert_eq!(request.to, Some(L1_SHARED_BRIDGE.into()));
+                            return if with_nullifier {
+                                Ok(L1_NULLIFIERCall::abi_encode_returns(&L1_NULLIFIER).into())
+                            } else {
+                                // The legacy shared bridge doesn't implement the function.
+                                Err(ErrorObjectOwned::owned(3, "execution reverted", None::<()>))
+                            };
+                        }
+                        let finalizer = if with_nullifier {
+                            L1_NULLIFIER
+                        } else {
+                            L1_SHARED_BRIDGE
+                        };
+                        assert_eq!(request.to, Some(finalizer.into()));
+                        let call = isWithdrawalFinalizedCall::abi_decode(input).unwrap();
+                        assert_eq!(call._chainId, U256::from(324));
+                        assert_eq!(call._l2BatchNumber, U256::from(L1_BATCH_NUMBER));
+                        assert_eq!(call._l2MessageIndex, U256::from(3000));
+                        Ok(isWithdrawalFinalizedCall::abi_encode_returns(&finalized).into())
+                    })
+                    .unwrap();
+            },
+            move |module| {
+                module
+                    .register_method::<RpcResult<serde_json::Value>, _>(
+                        "eth_getTransactionReceipt",
+                        move |_, _, _| Ok(receipt.clone()),
+                    )
+                    .unwrap();
+                module
+                    .register_method::<RpcResult<Option<L2ToL1LogProof>>, _>(
+                        "zks_getL2ToL1LogProof",
+                        move |_, _, _| Ok(in_batch.then(l2_to_l1_log_proof)),
+                    )
+                    .unwrap();
+                module
+                    .register_method::<RpcResult<Option<L1BatchDetails>>, _>(
+                        "zks_getL1BatchDetails",
+                        move |params, _, _| {
+                            let (batch_number,) = params.parse::<(u64,)>().unwrap();
+                            assert_eq!(batch_number, L1_BATCH_NUMBER);
+                            Ok(Some(l1_batch_details(executed)))
+                        },
+                    )
+                    .unwrap();
+                module
+                    .register_method::<RpcResult<BridgeAddresses>, _>(
+                        "zks_getBridgeContracts",
+                        move |_, _, _| Ok(shared_bridge_addresses()),
+                    )
+                    .unwrap();
+                module
+                    .register_method::<RpcResult<U64>, _>("eth_chainId", move |_, _, _| {
+                        Ok(U64::from(324))
+                    })
+                    .unwrap();
+            },
+            |l1_provider, l2_provider| async move {
+                l2_provider
+                    .is_withdrawal_finalized(WITHDRAWAL_TX_HASH, None, &l1_provider)
+                    .await
+                    .unwrap()
+            },
+        )
+        .await
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn withdrawal_is_not_ready_until_batch_is_executed() {
+        assert_eq!(
+            withdrawal_status(false, false, false, false).await,
+            WithdrawalStatus::NotReady
+        );
+        // Log proof is already available, but the batch is not executed on L1 yet.
+        assert_eq!(
+            withdrawal_status(true, false, false, false).await,
+            WithdrawalStatus::NotReady
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn withdrawal_is_not_finalized_once_batch_is_executed() {
+        assert_eq!(
+            withdrawal_status(true, true, false, false).await,
+            WithdrawalStatus::NotFinalized
+        );
+        assert_eq!(
+            withdrawal_status(true, true, false, true).await,
+            WithdrawalStatus::NotFinalized
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn withdrawal_is_finalized_on_l1() {
+        assert_eq!(
+            withdrawal_status(true, true, true, false).await,
+            WithdrawalStatus::Finalized
+        );
+        assert_eq!(
+            withdrawal_status(true, true, true, true).await,
+            WithdrawalStatus::Finalized
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn finalize_withdrawal_is_sent_to_l1_nullifier() {
+        use crate::contracts::l1::{
+            l1_bridge::L1Bridge::L1_NULLIFIERCall, l1_nullifier::L1Nullifier::finalizeDepositCall,
+        };
+        use alloy::sol_types::SolCall;
+        use jsonrpsee::types::ErrorObjectOwned;
+
+        let sender = address!("000000000000000000000000000000000000800a");
+        let message = Bytes::from(vec![0x6c, 0x09, 0x60, 0xf9, 1, 2, 3]);
+        let receipt = withdrawal_receipt_json(sender, &message, true);
+        let finalized = std::sync::Arc::new(std::sync::Mutex::new(None));
+        let finalized_rpc = finalized.clone();
+        run_l1_and_l2_servers_and_test(
+            move |module| {
+                module
+                    .register_method::<RpcResult<Bytes>, _>("eth_call", move |params, _, _| {
+                        let (request, _) = params
+                            .parse::<(alloy::rpc::types::TransactionRequest, serde_json::Value)>()
+                            .unwrap();
+                        assert_eq!(request.to, Some(L1_SHARED_BRIDGE.into()));
+                        L1_NULLIFIERCall::abi_decode(request.input.input().unwrap()).unwrap();
+                        Ok(L1_NULLIFIERCall::abi_encode_returns(&L1_NULLIFIER).into())
+                    })
+                    .unwrap();
+                module
+                    .register_method::<RpcResult<B256>, _>(
+                        "eth_sendTransaction",
+                        move |params, _, _| {
+                            let (request,) = params
+                                .parse::<(alloy::rpc::types::TransactionRequest,)>()
+                                .unwrap();
+                            assert_eq!(request.to, Some(L1_NULLIFIER.into()));
+                            let call =
+                                finalizeDepositCall::abi_decode(request.input.input().unwrap())
+                                    .unwrap();
+                            *finalized_rpc.lock().unwrap() = Some(call._finalizeWithdrawalParams);
+                            // Stop the finalization once the transaction is captured.
+                            Err(ErrorObjectOwned::owned(-32000, "rejected", None::<()>))
+                        },
+                    )
+                    .unwrap();
+            },
+            move |module| {
+                module
+                    .register_method::<RpcResult<serde_json::Value>, _>(
+                        "eth_getTransactionReceipt",
+                        move |_, _, _| Ok(receipt.clone()),
+                    )
+                    .unwrap();
+                module
+                    .register_method::<RpcResult<Option<L2ToL1LogProof>>, _>(
+                        "zks_getL2ToL1LogProof",
+                        move |_, _, _| Ok(Some(l2_to_l1_log_proof())),
+                    )
+                    .unwrap();
+                module
+                    .register_method::<RpcResult<BridgeAddresses>, _>(
+                        "zks_getBridgeContracts",
+                        move |_, _, _| Ok(shared_bridge_addresses()),
+                    )
+                    .unwrap();
+                module
+                    .register_method::<RpcResult<U64>, _>("eth_chainId", move |_, _, _| {
+                        Ok(U64::from(324))
+                    })
+                    .unwrap();
+            },
+            |l1_provider, l2_provider| async move {
+                let error = l2_provider
+                    .finalize_withdrawal(WITHDRAWAL_TX_HASH, &l1_provider)
+                    .await
+                    .unwrap_err();
+                assert!(matches!(error, L1CommunicationError::Rpc { .. }));
+            },
+        )
+        .await;
+
+        let params = finalized.lock().unwrap().take().unwrap();
+        assert_eq!(params.chainId, U256::from(324));
+        assert_eq!(params.l2BatchNumber, U256::from(L1_BATCH_NUMBER));
+        assert_eq!(params.l2MessageIndex, U256::from(3000));
+        assert_eq!(params.l2Sender, sender);
+        assert_eq!(params.l2TxNumberInBatch, 0x12d);
+        assert_eq!(params.message, message);
+        assert_eq!(params.merkleProof, l2_to_l1_log_proof().proof);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn claim_failed_deposit_params_are_recovered_from_finalize_deposit_calldata() {
+        use crate::contracts::l2::l2_bridge::encode_finalize_deposit_calldata;
+        use crate::utils::BOOTLOADER_FORMAL_ADDRESS;
+
+        let deposit_tx_hash = B256::repeat_byte(0xde);
+        let deposit_sender = address!("3333333333333333333333333333333333333333");
+        let l1_token = address!("5555555555555555555555555555555555555555");
+        let amount = U256::from(1000);
+        let receipt = l2_receipt_json(
+            deposit_tx_hash,
+            false,
+            true,
+            vec![],
+            vec![(BOOTLOADER_FORMAL_ADDRESS, deposit_tx_hash)],
+        );
+        let deposit_tx = Transaction {
+            common_data: ExecuteTransactionCommon::L1(L1TxCommonData {
+                sender: deposit_sender,
+                serial_id: 123,
+                layer_2_tip_fee: U256::ZERO,
+                full_fee: U256::from(2222223),
+                max_fee_per_gas: U256::from(2222224),
+                gas_limit: U256::from(2222225),
+                gas_per_pubdata_limit: U256::from(800),
+                op_processing_type: OpProcessingType::Common,
+                priority_queue_type: PriorityQueueType::Deque,
+                canonical_tx_hash: deposit_tx_hash,
+                to_mint: U256::from(222226),
+                refund_recipient: deposit_sender,
+            }),
+            execute: Execute {
+                contract_address: Some(L2_SHARED_BRIDGE),
+                calldata: encode_finalize_deposit_calldata(
+                    deposit_sender,
+                    address!("4444444444444444444444444444444444444444"),
+                    l1_token,
+                    amount,
+                    Bytes::new(),
+                ),
+                value: U256::ZERO,
+                factory_deps: vec![],
+            },
+            received_timestamp_ms: 30000,
+            raw_bytes: None,
+        };
+        run_l1_and_l2_servers_and_test(
+            |_| {},
+            |module| {
+                module
+                    .register_method::<RpcResult<serde_json::Value>, _>(
+                        "eth_getTransactionReceipt",
+                        move |_, _, _| Ok(receipt.clone()),
+                    )
+                    .unwrap();
+                module
+                    .register_method::<RpcResult<Option<L2ToL1LogProof>>, _>(
+                        "zks_getL2ToL1LogProof",
+                        move |params, _, _| {
+                            let (tx_hash, index) = params.parse::<(B256, Option<usize>)>().unwrap();
+                            assert_eq!(tx_hash, deposit_tx_hash);
+                            assert_eq!(index, Some(0));
+                            Ok(Some(l2_to_l1_log_proof()))
+                        },
+                    )
+                    .unwrap();
+                module
+                    .register_method::<RpcResult<Vec<Transaction>>, _>(
+                        "zks_getRawBlockTransactions",
+                        move |params, _, _| {
+                            let (block_number,) = params.parse::<(u64,)>().unwrap();
+                            assert_eq!(block_number, 0x1d1551e);
+                            Ok(vec![deposit_tx.clone()])
+                        },
+                    )
+                    .unwrap();
+            },
+            |l1_provider, l2_provider| async move {
+                let params =
+                    ClaimFailedDepositExecutor::new(&l1_provider, &l2_provider, deposit_tx_hash)
+                        .get_claim_failed_deposit_params()
+                        .await
+                        .unwrap();
+                assert_eq!(
+                    params,
+                    ClaimFailedDepositParams {
+                        deposit_sender,
+                        l1_token,
+                        amount,
+                        l1_batch_number: L1_BATCH_NUMBER,
+                        l2_message_index: 3000,
+                        l2_tx_number_in_batch: 0x12d,
+                        proof: l2_to_l1_log_proof().proof,
+                    }
+                );
+            },
+        )
+        .await;
+    }
+
+    const BRIDGEHUB: Address = address!("6666666666666666666666666666666666666666");
+    const L2_GAS_LIMIT: u64 = 500_000;
+    const L2_TX_BASE_COST: u64 = 1_000_000;
+    const L1_BASE_FEE: u128 = 1_000_000_000;
+    const L1_PRIORITY_FEE: u128 = 2_000_000;
+    const L1_GAS_ESTIMATION: u64 = 100_000;
+
+    /// Registers the L1 methods used to prepare L1 -> L2 transactions. ERC20 tokens report
+    /// the provided allowance and a sufficient balance.
+    fn register_l1_to_l2_l1_methods(module: &mut RpcModule<()>, allowance: U256) {
+        use crate::contracts::{
+            common::erc20::ERC20, l1::bridge_hub::Bridgehub::l2TransactionBaseCostCall,
+        };
+        use alloy::sol_types::{SolCall, SolValue};
+
+        module
+            .register_method::<RpcResult<U256>, _>("eth_maxPriorityFeePerGas", move |_, _, _| {
+                Ok(U256::from(L1_PRIORITY_FEE))
+            })
+            .unwrap();
+        module
+            .register_method::<RpcResult<serde_json::Value>, _>("eth_feeHistory", move |_, _, _| {
+                Ok(serde_json::json!({
+                    "oldestBlock": "0x1",
+                    "baseFeePerGas": [U256::from(L1_BASE_FEE), U256::from(L1_BASE_FEE)],
+                    "gasUsedRatio": [0.5],
+                    "reward": [["0x0"]],
+                }))
+            })
+            .unwrap();
+        module
+            .register_method::<RpcResult<U64>, _>("eth_estimateGas", move |_, _, _| {
+                Ok(U64::from(L1_GAS_ESTIMATION))
+            })
+            .unwrap();
+        module
+            .register_method::<RpcResult<Bytes>, _>("eth_call", move |params, _, _| {
+                let (request, _) = params
+                    .parse::<(alloy::rpc::types::TransactionRequest, serde_json::Value)>()
+                    .unwrap();
+                let input = request.input.input().unwrap();
+                let output = match input[..4].try_into().unwrap() {
+                    l2TransactionBaseCostCall::SELECTOR => {
+                        assert_eq!(request.to, Some(BRIDGEHUB.into()));
+                        U256::from(L2_TX_BASE_COST).abi_encode()
+                    }
+                    ERC20::allowanceCall::SELECTOR => allowance.abi_encode(),
+                    ERC20::balanceOfCall::SELECTOR => U256::MAX.abi_encode(),
+                    ERC20::nameCall::SELECTOR => "Token".abi_encode(),
+                    ERC20::symbolCall::SELECTOR => "TKN".abi_encode(),
+                    ERC20::decimalsCall::SELECTOR => U256::from(18).abi_encode(),
+                    selector => panic!("unexpected call {selector:?}"),
+                };
+                Ok(output.into())
+            })
+            .unwrap();
+    }
+
+    /// Registers the L2 methods used to prepare L1 -> L2 transactions on the chain
+    /// with the provided base token.
+    fn register_l1_to_l2_l2_methods(module: &mut RpcModule<()>, base_token: Address) {
+        module
+            .register_method::<RpcResult<U64>, _>("eth_chainId", move |_, _, _| Ok(U64::from(324)))
+            .unwrap();
+        module
+            .register_method::<RpcResult<Address>, _>(
+                "zks_getBaseTokenL1Address",
+                move |_, _, _| Ok(base_token),
+            )
+            .unwrap();
+        module
+            .register_method::<RpcResult<Option<Address>>, _>(
+                "zks_getBridgehubContract",
+                move |_, _, _| Ok(Some(BRIDGEHUB)),
+            )
+            .unwrap();
+        module
+            .register_method::<RpcResult<BridgeAddresses>, _>(
+                "zks_getBridgeContracts",
+                move |_, _, _| Ok(shared_bridge_addresses()),
+            )
+            .unwrap();
+        module
+            .register_method::<RpcResult<U256>, _>("zks_estimateGasL1ToL2", move |_, _, _| {
+                Ok(U256::from(L2_GAS_LIMIT))
+            })
+            .unwrap();
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn deposit_quote_estimates_costs_without_sending() {
+        use crate::contracts::l1::bridge_hub::Bridgehub::requestL2TransactionDirectCall;
+        use crate::utils::ETH_ADDRESS_IN_CONTRACTS;
+        use alloy::sol_types::SolCall;
+
+        let amount = U256::from(1000);
+        run_l1_and_l2_servers_and_test(
+            |module| register_l1_to_l2_l1_methods(module, U256::ZERO),
+            |module| register_l1_to_l2_l2_methods(module, ETH_ADDRESS_IN_CONTRACTS),
+            |l1_provider, l2_provider| async move {
+                let request = DepositRequest::new(amount);
+                let quote = DepositExecutor::new(&l1_provider, &l2_provider, &request)
+                    .quote()
+                    .await
+                    .unwrap();
+
+                let mint_value = U256::from(L2_TX_BASE_COST) + amount;
+                assert_eq!(quote.l2_gas_limit, U256::from(L2_GAS_LIMIT));
+                assert_eq!(quote.l2_tx_base_cost, U256::from(L2_TX_BASE_COST));
+                assert_eq!(quote.mint_value, mint_value);
+                assert_eq!(quote.l1_value, mint_value);
+                assert_eq!(quote.l1_gas_limit, Some(L1_GAS_ESTIMATION * 12 / 10));
+                assert_eq!(quote.max_fee_per_gas, L1_BASE_FEE * 3 / 2 + L1_PRIORITY_FEE);
+                assert_eq!(quote.max_priority_fee_per_gas, L1_PRIORITY_FEE);
+                assert!(quote.approvals.is_empty());
+
+                assert_eq!(quote.l1_tx_request.to, Some(BRIDGEHUB.into()));
+                let call = requestL2TransactionDirectCall::abi_decode(
+                    quote.l1_tx_request.input.input().unwrap(),
+                )
+                .unwrap();
+                assert_eq!(call.request.mintValue, mint_value);
+                assert_eq!(call.request.l2Value, amount);
+                assert_eq!(
+                    call.request.l2Contract,
+                    l2_provider.default_signer_address()
+                );
+            },
+        )
+        .await;
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn deposit_approves_required_amount_over_partial_allowance() {
+        use crate::contracts::common::erc20::ERC20::approveCall;
+        use crate::utils::ETH_ADDRESS_IN_CONTRACTS;
+        use alloy::sol_types::SolCall;
+        use jsonrpsee::types::ErrorObjectOwned;
+
+        let token = address!("5555555555555555555555555555555555555555");
+        let amount = U256::from(1000);
+        let allowance = U256::from(400);
+        let approved = std::sync::Arc::new(std::sync::Mutex::new(None));
+        let approved_rpc = approved.clone();
+        run_l1_and_l2_servers_and_test(
+            move |module| {
+                register_l1_to_l2_l1_methods(module, allowance);
+                module
+                    .register_method::<RpcResult<U256>, _>("eth_getBalance", move |_, _, _| {
+                        Ok(U256::MAX)
+                    })
+                    .unwrap();
+                module
+                    .register_method::<RpcResult<B256>, _>(
+                        "eth_sendTransaction",
+                        move |params, _, _| {
+                            let (request,) = params
+                                .parse::<(alloy::rpc::types::TransactionRequest,)>()
+                                .unwrap();
+                            assert_eq!(request.to, Some(token.into()));
+                            let call =
+                                approveCall::abi_decode(request.input.input().unwrap()).unwrap();
+                            *approved_rpc.lock().unwrap() = Some((call.spender, call.value));
+                            // Stop the deposit once the approval is captured.
+                            Err(ErrorObjectOwned::owned(-32000, "rejected", None::<()>))
+                        },
+                    )
+                    .unwrap();
+            },
+            |module| register_l1_to_l2_l2_methods(module, ETH_ADDRESS_IN_CONTRACTS),
+            |l1_provider, l2_provider| async move {
+                let request = DepositRequest::new(amount).with_token(token);
+                let executor = DepositExecutor::new(&l1_provider, &l2_provider, &request);
+
+                let quote = executor.quote().await.unwrap();
+                assert_eq!(
+                    quote.approvals,
+                    vec![DepositApproval {
+                        token,
+                        spender: L1_SHARED_BRIDGE,
+                        amount,
+                        allowance,
+                    }]
+                );
+                assert_eq!(quote.l1_gas_limit, None);
+
+                // Approval replaces the allowance, so the whole deposit amount has to be approved.
+                let Err(error) = executor.execute().await else {
+                    panic!("deposit must fail once the approval is rejected");
+                };
+                assert!(matches!(error, L1CommunicationError::Rpc { .. }));
+                assert_eq!(*approved.lock().unwrap(), Some((L1_SHARED_BRIDGE, amount)));
+
+                let request = request.with_auto_approval(false);
+                let Err(error) = DepositExecutor::new(&l1_provider, &l2_provider, &request)
+                    .execute()
+                    .await
+                else {
+                    panic!("deposit must fail without approval");
+                };
+                assert!(matches!(
+                    error,
+                    L1CommunicationError::InsufficientAllowance { required, available, .. }
+                        if required == amount && available == allowance
+                ));
+            },
+        )
+        .await;
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn request_execute_mint_value_depends_on_base_token() {
+        use crate::contracts::l1::bridge_hub::Bridgehub::requestL2TransactionDirectCall;
+        use crate::utils::ETH_ADDRESS_IN_CONTRACTS;
+        use alloy::sol_types::SolCall;
+
+        let contract = address!("7777777777777777777777777777777777777777");
+        let l2_value = U256::from(1000);
+        let mint_value = U256::from(L2_TX_BASE_COST) + l2_value;
+        let request = RequestExecuteRequest::new(contract, Bytes::from_static(&[0xab, 0xcd]))
+            .with_l2_value(l2_value);
+
+        // ETH-based chain: the base token is sent as the transaction value.
+        let request_eth = request.clone();
+        let prepared = run_l1_and_l2_servers_and_test(
+            |module| register_l1_to_l2_l1_methods(module, U256::ZERO),
+            |module| register_l1_to_l2_l2_methods(module, ETH_ADDRESS_IN_CONTRACTS),
+            |l1_provider, l2_provider| async move {
+                RequestExecuteExecutor::new(&l1_provider, &l2_provider, &request_eth)
+                    .prepare()
+                    .await
+                    .unwrap()
+            },
+        )
+        .await;
+        assert_eq!(prepared.tx_request.value, Some(mint_value));
+        assert!(prepared.approval.is_none());
+        let call =
+            requestL2TransactionDirectCall::abi_decode(prepared.tx_request.input.input().unwrap())
+                .unwrap();
+        assert_eq!(call.request.mintValue, mint_value);
+        assert_eq!(call.request.l2Value, l2_value);
+        assert_eq!(call.request.l2Contract, contract);
+
+        // Custom base token chain: the base token is transferred by the bridge, so
+        // it has to be approved and no value is sent.
+        let base_token = address!("5555555555555555555555555555555555555555");
+        let allowance = U256::from(400);
+        let prepared = run_l1_and_l2_servers_and_test(
+            move |module| register_l1_to_l2_l1_methods(module, allowance),
+            move |module| register_l1_to_l2_l2_methods(module, base_token),
+            |l1_provider, l2_provider| async move {
+                RequestExecuteExecutor::new(&l1_provider, &l2_provider, &request)
+                    .prepare()
+                    .await
+                    .unwrap()
+            },
+        )
+        .await;
+        assert_eq!(prepared.tx_request.value, Some(U256::ZERO));
+        assert_eq!(
+            prepared.approval,
+            Some(DepositApproval {
+                token: base_token,
+                spender: L1_SHARED_BRIDGE,
+                amount: mint_value,
+                allowance,
+            })
+        );
+        let call =
+            requestL2TransactionDirectCall::abi_decode(prepared.tx_request.input.input().unwrap())
+                .unwrap();
+        assert_eq!(call.request.mintValue, mint_value);
+        assert_eq!(call.request.l2Value, l2_value);
+    }
+
+    /// Registers the methods used to watch the finality of [`WITHDRAWAL_TX_HASH`] with
+    /// the provided L2 status, included in the [`L1_BATCH_NUMBER`] batch.
+    fn register_finality_methods(
+        module: &mut RpcModule<()>,
+        tx_status: TransactionStatus,
+        executed: bool,
+    ) {
+        module
+            .register_method::<RpcResult<Option<TransactionDetails>>, _>(
+                "zks_getTransactionDetails",
+                move |params, _, _| {
+                    let (tx_hash,) = params.parse::<(B256,)>().unwrap();
+                    assert_eq!(tx_hash, WITHDRAWAL_TX_HASH);
+                    Ok(Some(TransactionDetails {
+                        is_l1_originated: false,
+                        status: tx_status.clone(),
+                        fee: U256::from(10000),
+                        gas_per_pubdata: U256::from(800),
+                        initiator_address: address!("3333333333333333333333333333333333333333"),
+                        received_at: str_to_utc("2024-03-28T18:18:04.204270Z"),
+                        eth_commit_tx_hash: None,
+                        eth_prove_tx_hash: None,
+                        eth_execute_tx_hash: None,
+                    }))
+                },
+            )
+            .unwrap();
+        module
+            .register_method::<RpcResult<serde_json::Value>, _>(
+                "eth_getTransactionReceipt",
+                move |_, _, _| {
+                    Ok(l2_receipt_json(
+                        WITHDRAWAL_TX_HASH,
+                        true,
+                        true,
+                        vec![],
+                        vec![],
+                    ))
+                },
+            )
+            .unwrap();
+        module
+            .register_method::<RpcResult<Option<BlockDetails>>, _>(
+                "zks_getBlockDetails",
+                move |params, _, _| {
+                    let (block_number,) = params.parse::<(u64,)>().unwrap();
+                    assert_eq!(block_number, 0x1d1551e);
+                    let batch = l1_batch_details(executed);
+                    Ok(Some(BlockDetails {
+                        number: block_number,
+                        l1_batch_number: L1_BATCH_NUMBER,
+                        operator_address: address!("4444444444444444444444444444444444444444"),
+                        protocol_version: Some("Version24".to_string()),
+                        timestamp: batch.timestamp,
+                        l1_tx_count: 0,
+                        l2_tx_count: 1,
+                        root_hash: batch.root_hash,
+                        status: batch.status,
+                        commit_tx_hash: batch.commit_tx_hash,
+                        committed_at: batch.committed_at,
+                        prove_tx_hash: batch.prove_tx_hash,
+                        proven_at: batch.proven_at,
+                        execute_tx_hash: batch.execute_tx_hash,
+                        executed_at: batch.executed_at,
+                        l1_gas_price: batch.l1_gas_price,
+                        l2_fair_gas_price: batch.l2_fair_gas_price,
+                        fair_pubdata_price: batch.fair_pubdata_price,
+                        base_system_contracts_hashes: batch.base_system_contracts_hashes,
+                    }))
+                },
+            )
+            .unwrap();
+        module
+            .register_method::<RpcResult<Option<L1BatchDetails>>, _>(
+                "zks_getL1BatchDetails",
+                move |params, _, _| {
+                    let (batch_number,) = params.parse::<(u64,)>().unwrap();
+                    assert_eq!(batch_number, L1_BATCH_NUMBER);
+                    Ok(Some(l1_batch_details(executed)))
+                },
+            )
+            .unwrap();
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn finality_watcher_resolves_once_level_is_reached() {
+        run_server_and_test(
+            |module| register_finality_methods(module, TransactionStatus::Verified, false),
+            |provider: ZKsyncTestProvider| async move {
+                let pending_tx = alloy::providers::PendingTransactionBuilder::new(
+                    provider.root().clone(),
+                    WITHDRAWAL_TX_HASH,
+                );
+                let status = pending_tx
+                    .with_finality(FinalityLevel::Committed)
+                    .with_poll_interval(std::time::Duration::from_millis(10))
+                    .watch()
+                    .await
+                    .unwrap();
+                assert_eq!(status.block.l1_batch_number, L1_BATCH_NUMBER);
+                assert_eq!(status.l1_batch, Some(l1_batch_details(false)));
+
+                // The batch is only committed, so the execution is never reached.
+                let error = FinalityWatcher::new(
+                    provider.root().clone(),
+                    WITHDRAWAL_TX_HASH,
+                    FinalityLevel::Executed,
+                )
+                .with_poll_interval(std::time::Duration::from_millis(10))
+                .with_timeout(Some(std::time::Duration::from_millis(200)))
+                .watch()
+                .await
+                .unwrap_err();
+                assert!(matches!(
+                    error,
+                    L1CommunicationError::Timeout(hash) if hash == WITHDRAWAL_TX_HASH
+                ));
+            },
+        )
+        .await;
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn finality_watcher_fails_immediately_for_failed_transaction() {
+        run_server_and_test(
+            |module| register_finality_methods(module, TransactionStatus::Failed, true),
+            |provider: ZKsyncTestProvider| async move {
+                // No timeout is set, so the watcher would hang if it kept polling.
+                let error = FinalityWatcher::new(
+                    provider.root().clone(),
+                    WITHDRAWAL_TX_HASH,
+                    FinalityLevel::Sealed,
+                )
+                .watch()
+                .await
+                .unwrap_err();
+                assert!(matches!(
+                    error,
+                    L1CommunicationError::TransactionFailed(hash) if hash == WITHDRAWAL_TX_HASH
+                ));
+            },
+        )
+        .await;
+    }
+
+    #[test]
+    fn polling_futures_are_send() {
+        fn assert_send<T: Send>(_: &T) {}
+
+        let provider = zksync_provider().connect_http("http://localhost:3050".parse().unwrap());
+        let watcher = FinalityWatcher::new(provider.clone(), B256::ZERO, FinalityLevel::Sealed);
+        assert_send(&watcher.watch());
+        let tracker = PriorityOpTracker::new(provider, B256::ZERO);
+        assert_send(&tracker.wait_for(PriorityOpStage::Executed));
+    }
 }
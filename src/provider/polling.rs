@@ -0,0 +1,60 @@
+//! Polling of the L2 node until the awaited state of a transaction is reached.
+
+use crate::{
+    provider::L1CommunicationError,
+    utils::{sleep, timeout},
+};
+use alloy::primitives::B256;
+use std::time::Duration;
+
+/// Default interval between the status requests.
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Interval and timeout used to poll the status of a transaction.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Poller {
+    /// Interval between the status requests.
+    pub(crate) interval: Duration,
+    /// Timeout for waiting. If None, waiting is not limited in time.
+    pub(crate) timeout: Option<Duration>,
+}
+
+impl Default for Poller {
+    fn default() -> Self {
+        Self {
+            interval: DEFAULT_POLL_INTERVAL,
+            timeout: None,
+        }
+    }
+}
+
+impl Poller {
+    /// Calls `fetch` until `check` accepts the fetched state, sleeping for the interval
+    /// between the calls. Fails with [`L1CommunicationError::Timeout`] for `tx_hash` once
+    /// the timeout elapses.
+    pub(crate) async fn poll<S, T, Fut>(
+        &self,
+        tx_hash: B256,
+        mut fetch: impl FnMut() -> Fut,
+        mut check: impl FnMut(S) -> Option<T>,
+    ) -> Result<T, L1CommunicationError>
+    where
+        Fut: Future<Output = Result<S, L1CommunicationError>>,
+    {
+        let wait = async {
+            loop {
+                if let Some(value) = check(fetch().await?) {
+                    return Ok(value);
+                }
+                sleep(self.interval).await;
+            }
+        };
+
+        match self.timeout {
+            Some(duration) => timeout(duration, wait)
+                .await
+                .map_err(|_| L1CommunicationError::Timeout(tx_hash))?,
+            None => wait.await,
+        }
+    }
+}
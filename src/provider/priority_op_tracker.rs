@@ -0,0 +1,165 @@
+//! Tracking of the L1 -> L2 priority operations lifecycle.
+
+use crate::{
+    network::Zksync,
+    provider::{L1CommunicationError, ZksyncProvider, polling::Poller},
+    types::{TransactionDetails, TransactionStatus},
+};
+use alloy::{primitives::B256, providers::RootProvider};
+use std::time::Duration;
+
+/// Stage of the priority operation lifecycle.
+/// Stages are ordered, so that each stage implies all the previous ones.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PriorityOpStage {
+    /// The operation is not yet processed on L2.
+    Pending,
+    /// The operation is included in an L2 block.
+    Included,
+    /// The batch with the operation is committed on L1.
+    Committed,
+    /// The batch with the operation is proven on L1.
+    Proven,
+    /// The batch with the operation is executed on L1.
+    Executed,
+}
+
+/// Current status of the priority operation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PriorityOpStatus {
+    /// Reached lifecycle stage.
+    pub stage: PriorityOpStage,
+    /// Whether the L2 execution of the operation failed.
+    /// Failed operations still go through all the stages.
+    pub failed: bool,
+    /// Hash of the L1 transaction that committed the batch with the operation.
+    pub eth_commit_tx_hash: Option<B256>,
+    /// Hash of the L1 transaction that proved the batch with the operation.
+    pub eth_prove_tx_hash: Option<B256>,
+    /// Hash of the L1 transaction that executed the batch with the operation.
+    pub eth_execute_tx_hash: Option<B256>,
+}
+
+impl PriorityOpStatus {
+    fn pending() -> Self {
+        Self {
+            stage: PriorityOpStage::Pending,
+            failed: false,
+            eth_commit_tx_hash: None,
+            eth_prove_tx_hash: None,
+            eth_execute_tx_hash: None,
+        }
+    }
+}
+
+impl From<TransactionDetails> for PriorityOpStatus {
+    fn from(details: TransactionDetails) -> Self {
+        let stage = if details.eth_execute_tx_hash.is_some() {
+            PriorityOpStage::Executed
+        } else if details.eth_prove_tx_hash.is_some() {
+            PriorityOpStage::Proven
+        } else if details.eth_commit_tx_hash.is_some() {
+            PriorityOpStage::Committed
+        } else if details.status == TransactionStatus::Pending {
+            PriorityOpStage::Pending
+        } else {
+            PriorityOpStage::Included
+        };
+        Self {
+            stage,
+            failed: details.status == TransactionStatus::Failed,
+            eth_commit_tx_hash: details.eth_commit_tx_hash,
+            eth_prove_tx_hash: details.eth_prove_tx_hash,
+            eth_execute_tx_hash: details.eth_execute_tx_hash,
+        }
+    }
+}
+
+/// Type that follows the priority operation through L2 inclusion, batch commit, proof and execution on L1.
+pub struct PriorityOpTracker {
+    l2_provider: RootProvider<Zksync>,
+    l2_tx_hash: B256,
+    poller: Poller,
+}
+
+impl PriorityOpTracker {
+    /// Creates a tracker for the priority operation with the provided L2 transaction hash.
+    pub fn new(l2_provider: RootProvider<Zksync>, l2_tx_hash: B256) -> Self {
+        Self {
+            l2_provider,
+            l2_tx_hash,
+            poller: Poller::default(),
+        }
+    }
+
+    /// Sets the interval between the status requests.
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poller.interval = poll_interval;
+        self
+    }
+
+    /// Sets the timeout for waiting. If not set, waiting is not limited in time.
+    pub fn with_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.poller.timeout = timeout;
+        self
+    }
+
+    /// Returns the hash of the tracked L2 transaction.
+    pub fn tx_hash(&self) -> B256 {
+        self.l2_tx_hash
+    }
+
+    /// Fetches the current status of the priority operation.
+    pub async fn status(&self) -> Result<PriorityOpStatus, L1CommunicationError> {
+        let details = self
+            .l2_provider
+            .get_transaction_details(self.l2_tx_hash)
+            .await
+            .map_err(L1CommunicationError::rpc(
+                "fetching the transaction details",
+            ))?;
+        Ok(details.map_or_else(PriorityOpStatus::pending, Into::into))
+    }
+
+    /// Waits until the priority operation reaches the provided stage.
+    ///
+    /// ## Returns
+    ///
+    /// Status of the operation once it reached the stage.
+    pub async fn wait_for(
+        &self,
+        stage: PriorityOpStage,
+    ) -> Result<PriorityOpStatus, L1CommunicationError> {
+        self.wait_for_with(stage, |_| {}).await
+    }
+
+    /// Waits until the priority operation reaches the provided stage, calling `on_update`
+    /// every time the status changes, e.g. when a new L1 transaction hash appears.
+    ///
+    /// ## Returns
+    ///
+    /// Status of the operation once it reached the stage.
+    pub async fn wait_for_with(
+        &self,
+        stage: PriorityOpStage,
+        mut on_update: impl FnMut(&PriorityOpStatus),
+    ) -> Result<PriorityOpStatus, L1CommunicationError> {
+        let mut last_status = None;
+        self.poller
+            .poll(
+                self.l2_tx_hash,
+                || self.status(),
+                |status| {
+                    if last_status.as_ref() != Some(&status) {
+                        on_update(&status);
+                    }
+                    if status.stage >= stage {
+                        return Some(status);
+                    }
+                    last_status = Some(status);
+                    None
+                },
+            )
+            .await
+    }
+}
@@ -0,0 +1,276 @@
+//! Implementation of the generic L1 -> L2 transaction execution logic.
+
+use crate::{
+    contracts::l1::bridge_hub::L2TransactionRequestDirect,
+    network::{Zksync, transaction_request::TransactionRequest},
+    provider::{
+        DepositApproval, L1CommunicationError, ZksyncProvider,
+        l1_to_l2::{
+            FeeParams, REQUIRED_L1_TO_L2_GAS_PER_PUBDATA_LIMIT, approve_tokens,
+            get_bridgehub_contract, get_l1_fee_params, get_l2_tx_base_cost, get_required_approval,
+            submit_l1_to_l2_tx,
+        },
+        l1_transaction_receipt::L1TransactionReceipt,
+    },
+    utils::is_eth_l1_address,
+};
+use alloy::{
+    network::{Ethereum, NetworkWallet, TransactionBuilder},
+    primitives::{Address, Bytes, U256},
+    providers::WalletProvider,
+    rpc::types::eth::TransactionRequest as L1TransactionRequest,
+};
+
+/// Type for the request to execute an arbitrary L2 call from L1.
+/// This type only stores the required information for the call, while the execution itself
+/// is performed via [`RequestExecuteExecutor`].
+#[derive(Clone, Debug)]
+pub struct RequestExecuteRequest {
+    /// L2 contract to call.
+    pub contract_address: Address,
+    /// Calldata of the L2 call.
+    pub calldata: Bytes,
+    /// Value of the L2 call in Wei.
+    pub l2_value: U256,
+    /// Factory dependencies of the L2 call.
+    pub factory_deps: Vec<Bytes>,
+    /// Receiver of the L2 fee refund. If None, the sender address will be used as a recipient.
+    pub refund_recipient: Option<Address>,
+    /// Gas limit of the L2 call. If None, it will be estimated.
+    pub l2_gas_limit: Option<U256>,
+    /// Gas per pubdata limit to use in initiated transactions. If None,
+    /// REQUIRED_L1_TO_L2_GAS_PER_PUBDATA_LIMIT will be used.
+    pub gas_per_pubdata_limit: U256,
+    /// Enable or disable automatic submission of the base token approval transaction
+    /// on chains with a custom base token if the allowance is not sufficient.
+    pub auto_approval: bool,
+}
+
+impl RequestExecuteRequest {
+    /// Initiates a new request to call the L2 contract with the provided calldata.
+    pub fn new(contract_address: Address, calldata: Bytes) -> Self {
+        Self {
+            contract_address,
+            calldata,
+            l2_value: U256::ZERO,
+            factory_deps: vec![],
+            refund_recipient: None,
+            l2_gas_limit: None,
+            gas_per_pubdata_limit: U256::from(REQUIRED_L1_TO_L2_GAS_PER_PUBDATA_LIMIT),
+            auto_approval: true,
+        }
+    }
+
+    /// Sets the value of the L2 call.
+    pub fn with_l2_value(mut self, l2_value: U256) -> Self {
+        self.l2_value = l2_value;
+        self
+    }
+
+    /// Sets the factory dependencies of the L2 call.
+    pub fn with_factory_deps(mut self, factory_deps: Vec<Bytes>) -> Self {
+        self.factory_deps = factory_deps;
+        self
+    }
+
+    /// Sets the receiver of the L2 fee refund.
+    pub fn with_refund_recipient(mut self, refund_recipient: Address) -> Self {
+        self.refund_recipient = Some(refund_recipient);
+        self
+    }
+
+    /// Sets the gas limit of the L2 call, skipping the estimation.
+    pub fn with_l2_gas_limit(mut self, l2_gas_limit: U256) -> Self {
+        self.l2_gas_limit = Some(l2_gas_limit);
+        self
+    }
+
+    /// Sets the gas per pubdata limit for the transaction.
+    pub fn with_gas_per_pubdata_limit(mut self, value: U256) -> Self {
+        self.gas_per_pubdata_limit = value;
+        self
+    }
+
+    /// Enables or disables auto-approval of the base token on chains with a custom base token.
+    pub fn with_auto_approval(mut self, auto_approval: bool) -> Self {
+        self.auto_approval = auto_approval;
+        self
+    }
+}
+
+/// L1 transaction of the request along with the data required to submit it.
+pub(crate) struct PreparedRequestExecute {
+    pub(crate) sender: Address,
+    pub(crate) fee_params: FeeParams,
+    pub(crate) tx_request: L1TransactionRequest,
+    /// Base token approval required on chains with a custom base token.
+    pub(crate) approval: Option<DepositApproval>,
+}
+
+/// Type that handles execution of arbitrary L2 calls from L1.
+pub struct RequestExecuteExecutor<'a, P1, P2>
+where
+    P1: alloy::providers::Provider<Ethereum>,
+    P2: ZksyncProvider + WalletProvider<Zksync> + ?Sized,
+    P2::Wallet: NetworkWallet<Ethereum>,
+{
+    l1_provider: &'a P1,
+    l2_provider: &'a P2,
+    request: &'a RequestExecuteRequest,
+}
+
+impl<'a, P1, P2> RequestExecuteExecutor<'a, P1, P2>
+where
+    P1: alloy::providers::Provider<Ethereum>,
+    P2: ZksyncProvider + WalletProvider<Zksync> + ?Sized,
+    P2::Wallet: NetworkWallet<Ethereum>,
+{
+    /// Prepares an executor for a particular request.
+    pub fn new(
+        l1_provider: &'a P1,
+        l2_provider: &'a P2,
+        request: &'a RequestExecuteRequest,
+    ) -> Self {
+        RequestExecuteExecutor {
+            l1_provider,
+            l2_provider,
+            request,
+        }
+    }
+
+    async fn get_l2_gas_limit(&self, sender: Address) -> Result<U256, L1CommunicationError> {
+        if let Some(l2_gas_limit) = self.request.l2_gas_limit {
+            return Ok(l2_gas_limit);
+        }
+        self.l2_provider
+            .estimate_gas_l1_to_l2(
+                TransactionRequest::default()
+                    .with_from(sender)
+                    .with_to(self.request.contract_address)
+                    .with_value(self.request.l2_value)
+                    .with_input(self.request.calldata.clone())
+                    .with_gas_per_pubdata(self.request.gas_per_pubdata_limit)
+                    .with_factory_deps(self.request.factory_deps.clone()),
+            )
+            .await
+            .map_err(L1CommunicationError::rpc(
+                "estimating gas for L1 -> L2 transaction",
+            ))
+    }
+
+    /// Returns the spender of the base token, or None if the chain is ETH-based and
+    /// the base token is sent as the transaction value instead.
+    async fn get_base_token_spender(
+        &self,
+        base_token: Address,
+    ) -> Result<Option<Address>, L1CommunicationError> {
+        if is_eth_l1_address(base_token) {
+            return Ok(None);
+        }
+        let bridge_addresses = self
+            .l2_provider
+            .get_bridge_contracts()
+            .await
+            .map_err(L1CommunicationError::rpc("fetching bridge contracts"))?;
+        bridge_addresses
+            .l1_shared_default_bridge
+            .ok_or(L1CommunicationError::MissingBridgeConfiguration(
+                "L1 shared default bridge is not defined for the chain with a custom base token.",
+            ))
+            .map(Some)
+    }
+
+    pub(crate) async fn prepare(&self) -> Result<PreparedRequestExecute, L1CommunicationError> {
+        let l2_chain_id = U256::from(
+            self.l2_provider
+                .get_chain_id()
+                .await
+                .map_err(L1CommunicationError::rpc("fetching L2 chain id"))?,
+        );
+        // Custom accounts can't send L1 transactions, so the default EOA is used as the sender.
+        let sender = NetworkWallet::<Ethereum>::default_signer_address(self.l2_provider.wallet());
+        let refund_recipient = self.request.refund_recipient.unwrap_or(sender);
+
+        let fee_params = get_l1_fee_params(self.l1_provider).await?;
+        let bridge_hub_contract =
+            get_bridgehub_contract(self.l1_provider, self.l2_provider).await?;
+        let l2_gas_limit = self.get_l2_gas_limit(sender).await?;
+        let tx_base_cost = get_l2_tx_base_cost(
+            &bridge_hub_contract,
+            l2_chain_id,
+            &fee_params,
+            l2_gas_limit,
+            self.request.gas_per_pubdata_limit,
+        )
+        .await?;
+
+        let base_token = self
+            .l2_provider
+            .get_base_token_l1_address()
+            .await
+            .map_err(L1CommunicationError::rpc("fetching the base token address"))?;
+        let base_token_spender = self.get_base_token_spender(base_token).await?;
+
+        // The base token is minted on L2 to cover both the fee and the L2 value. On ETH-based
+        // chains it is sent as the transaction value, otherwise it is transferred by the bridge.
+        let mint_value = tx_base_cost + self.request.l2_value;
+        let (l1_value, approval) = match base_token_spender {
+            None => (mint_value, None),
+            Some(spender) => (
+                U256::ZERO,
+                get_required_approval(self.l1_provider, base_token, sender, spender, mint_value)
+                    .await?,
+            ),
+        };
+
+        let tx_request = bridge_hub_contract
+            .requestL2TransactionDirect(L2TransactionRequestDirect {
+                chainId: l2_chain_id,
+                mintValue: mint_value,
+                l2Contract: self.request.contract_address,
+                l2Value: self.request.l2_value,
+                l2Calldata: self.request.calldata.clone(),
+                l2GasLimit: l2_gas_limit,
+                l2GasPerPubdataByteLimit: self.request.gas_per_pubdata_limit,
+                factoryDeps: self.request.factory_deps.clone(),
+                refundRecipient: refund_recipient,
+            })
+            .from(sender)
+            .value(l1_value)
+            .into_transaction_request()
+            .max_fee_per_gas(fee_params.max_fee_per_gas)
+            .max_priority_fee_per_gas(fee_params.max_priority_fee_per_gas);
+
+        Ok(PreparedRequestExecute {
+            sender,
+            fee_params,
+            tx_request,
+            approval,
+        })
+    }
+
+    /// Executes specified request. This will handle:
+    /// - Estimating the L2 gas limit and the L2 transaction base cost.
+    /// - Approving the base token for the bridge on chains with a custom base token
+    ///   (if auto-approval is enabled).
+    /// - Sending the `requestL2TransactionDirect` transaction to the bridgehub.
+    /// - Returning the [`L1TransactionReceipt`] of the sent transaction.
+    ///
+    /// ## Returns
+    ///
+    /// L1TransactionReceipt of the L1 -> L2 transaction.
+    pub async fn execute(&self) -> Result<L1TransactionReceipt, L1CommunicationError> {
+        let prepared = self.prepare().await?;
+        let approvals: Vec<_> = prepared.approval.into_iter().collect();
+        approve_tokens(
+            self.l1_provider,
+            prepared.sender,
+            &approvals,
+            self.request.auto_approval,
+            &prepared.fee_params,
+        )
+        .await?;
+
+        submit_l1_to_l2_tx(self.l1_provider, self.l2_provider, &prepared.tx_request).await
+    }
+}
@@ -0,0 +1,180 @@
+//! Implementation of the withdrawal logic.
+
+use crate::{
+    contracts::l2::{
+        l2_base_token::{L2_BASE_TOKEN_ADDRESS, encode_base_token_withdraw_calldata},
+        l2_bridge::encode_withdraw_calldata,
+    },
+    network::{
+        Zksync, transaction_request::TransactionRequest, unsigned_tx::eip712::PaymasterParams,
+    },
+    provider::{
+        L1CommunicationError, ZksyncProvider, l2_transaction_receipt::L2TransactionReceipt,
+    },
+};
+use alloy::{
+    network::{NetworkWallet, TransactionBuilder},
+    primitives::{Address, U256},
+    providers::WalletProvider,
+};
+
+/// Type for withdrawal request.
+/// This type only stores the required information for the withdrawal, while the withdrawal itself
+/// is performed via [`WithdrawExecutor`].
+#[derive(Clone, Debug)]
+pub struct WithdrawRequest {
+    /// Amount to withdraw in Wei.
+    pub amount: U256,
+    /// Receiver of withdrawn assets on L1. If None, the sender address will be used as a receiver.
+    pub receiver: Option<Address>,
+    /// L2 token address to withdraw.
+    pub token: Address,
+    /// L2 bridge address for the withdrawal. If None, default shared bridge will be used.
+    /// Ignored for base token withdrawals.
+    pub bridge_address: Option<Address>,
+    /// Paymaster parameters to use in the withdrawal transaction.
+    pub paymaster_params: Option<PaymasterParams>,
+}
+
+impl WithdrawRequest {
+    /// Initiates a new withdrawal request of the base token.
+    pub fn new(amount: U256) -> Self {
+        Self {
+            amount,
+            receiver: None,
+            token: L2_BASE_TOKEN_ADDRESS,
+            bridge_address: None,
+            paymaster_params: None,
+        }
+    }
+
+    /// Returns the amount to withdraw.
+    pub fn amount(&self) -> &U256 {
+        &self.amount
+    }
+
+    /// Sets the receiver for the withdrawal.
+    pub fn with_receiver(mut self, address: Address) -> Self {
+        self.receiver = Some(address);
+        self
+    }
+
+    /// Sets the L2 token address for the withdrawal.
+    pub fn with_token(mut self, token: Address) -> Self {
+        self.token = token;
+        self
+    }
+
+    /// Sets the L2 bridge address.
+    pub fn with_bridge_address(mut self, bridge_address: Address) -> Self {
+        self.bridge_address = Some(bridge_address);
+        self
+    }
+
+    /// Sets the paymaster parameters for the withdrawal transaction.
+    pub fn with_paymaster_params(mut self, paymaster_params: PaymasterParams) -> Self {
+        self.paymaster_params = Some(paymaster_params);
+        self
+    }
+}
+
+/// Type that handles withdrawal logic for various scenarios: withdraw base token, ERC20 etc.
+pub struct WithdrawExecutor<'a, P>
+where
+    P: ZksyncProvider + WalletProvider<Zksync> + ?Sized,
+{
+    l2_provider: &'a P,
+    request: &'a WithdrawRequest,
+}
+
+impl<'a, P> WithdrawExecutor<'a, P>
+where
+    P: ZksyncProvider + WalletProvider<Zksync> + ?Sized,
+{
+    /// Prepares an executor for a particular withdrawal request.
+    pub fn new(l2_provider: &'a P, request: &'a WithdrawRequest) -> Self {
+        WithdrawExecutor {
+            l2_provider,
+            request,
+        }
+    }
+
+    async fn get_l2_bridge_address(&self) -> Result<Address, L1CommunicationError> {
+        if let Some(bridge_address) = self.request.bridge_address {
+            return Ok(bridge_address);
+        }
+        let bridge_addresses = self
+            .l2_provider
+            .get_bridge_contracts()
+            .await
+            .map_err(L1CommunicationError::rpc("fetching bridge contracts"))?;
+        bridge_addresses.l2_shared_default_bridge.ok_or(
+            L1CommunicationError::MissingBridgeConfiguration(
+                "L2 shared default bridge is not defined for the chain and bridge address is not specified in the withdrawal request.",
+            ),
+        )
+    }
+
+    pub(crate) async fn get_l2_withdraw_tx(
+        &self,
+        sender: Address,
+        receiver: Address,
+    ) -> Result<TransactionRequest, L1CommunicationError> {
+        let tx_request = if self.request.token == L2_BASE_TOKEN_ADDRESS {
+            TransactionRequest::default()
+                .with_from(sender)
+                .with_to(L2_BASE_TOKEN_ADDRESS)
+                .with_value(self.request.amount)
+                .with_input(encode_base_token_withdraw_calldata(receiver))
+        } else {
+            let l2_bridge_address = self.get_l2_bridge_address().await?;
+            TransactionRequest::default()
+                .with_from(sender)
+                .with_to(l2_bridge_address)
+                .with_input(encode_withdraw_calldata(
+                    receiver,
+                    self.request.token,
+                    self.request.amount,
+                ))
+        };
+
+        Ok(match &self.request.paymaster_params {
+            Some(paymaster_params) => tx_request.with_paymaster_params(paymaster_params.clone()),
+            None => tx_request,
+        })
+    }
+
+    /// Executes specified withdrawal request. This will handle:
+    /// - Sending the withdrawal transaction on L2.
+    /// - Returning the [`L2TransactionReceipt`] of the withdrawal transaction.
+    ///
+    /// Once the L1 batch containing the withdrawal is executed on L1, the withdrawal has to be
+    /// finalized on L1 to receive the funds.
+    ///
+    /// ## Returns
+    ///
+    /// L2TransactionReceipt of the withdrawal transaction.
+    pub async fn execute(&self) -> Result<L2TransactionReceipt, L1CommunicationError> {
+        let sender = self.l2_provider.wallet().default_signer_address();
+        let receiver = self.request.receiver.unwrap_or(sender);
+
+        let l2_withdraw_tx = self.get_l2_withdraw_tx(sender, receiver).await?;
+        let l2_tx_receipt = self
+            .l2_provider
+            .send_transaction(l2_withdraw_tx)
+            .await
+            .map_err(L1CommunicationError::rpc(
+                "sending the L2 -> L1 withdrawal transaction",
+            ))?
+            .get_receipt()
+            .await
+            .map_err(L1CommunicationError::pending(
+                "getting the L2 -> L1 withdrawal transaction receipt",
+            ))?;
+
+        Ok(L2TransactionReceipt::new(
+            l2_tx_receipt,
+            self.l2_provider.root().clone(),
+        ))
+    }
+}
@@ -282,6 +282,22 @@ pub struct ProtocolVersion {
     pub l2_system_upgrade_tx_hash: Option<B256>,
 }
 
+impl ProtocolVersion {
+    /// Returns the code hash of the EVM emulator, if it's enabled.
+    pub fn evm_emulator_hash(&self) -> Option<B256> {
+        self.base_system_contracts
+            .as_ref()
+            .and_then(|contracts| contracts.evm_emulator)
+            .or(self.evm_emulator_code_hash)
+            .filter(|hash| !hash.is_zero())
+    }
+
+    /// Returns `true` if the EVM emulator is enabled, i.e. EVM bytecode can be deployed.
+    pub fn is_evm_emulator_enabled(&self) -> bool {
+        self.evm_emulator_hash().is_some()
+    }
+}
+
 /// Merkle proof for a storage value.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct StorageProof {
@@ -426,6 +442,7 @@ pub enum PriorityQueueType {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
 pub struct L1TxCommonData {
     /// Sender of the transaction.
     pub sender: Address,
@@ -466,10 +483,12 @@ pub struct L2TxCommonData {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
 pub struct ProtocolUpgradeTxCommonData {
     /// Sender of the transaction.
     pub sender: Address,
-    /// ID of the upgrade.
+    /// ID of the upgrade. The node reports it as the number of the protocol version.
+    #[serde(deserialize_with = "deserialize_upgrade_id")]
     pub upgrade_id: String,
     /// The maximal fee per gas to be used for L1->L2 transaction
     pub max_fee_per_gas: U256,
@@ -487,6 +506,24 @@ pub struct ProtocolUpgradeTxCommonData {
     pub refund_recipient: Address,
 }
 
+/// Accepts the upgrade ID either as the protocol version number or as a string.
+fn deserialize_upgrade_id<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum UpgradeId {
+        Number(u64),
+        String(String),
+    }
+
+    Ok(match UpgradeId::deserialize(deserializer)? {
+        UpgradeId::Number(version) => version.to_string(),
+        UpgradeId::String(id) => id,
+    })
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub enum ExecuteTransactionCommon {
     L1(L1TxCommonData),
@@ -6,12 +6,35 @@ use alloy::{
 };
 use std::str::FromStr;
 
+#[cfg(not(target_family = "wasm"))]
+pub(crate) use tokio::time::{sleep, timeout};
+#[cfg(target_family = "wasm")]
+pub(crate) use wasmtimer::tokio::{sleep, timeout};
+
 /// ETH address on L1.
 pub const ETHER_L1_ADDRESS: Address = Address::new([
     0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
     0x00, 0x00, 0x00, 0x00,
 ]);
 
+/// Address used by the L1 contracts to represent ETH, e.g. as the base token of ETH-based chains.
+pub const ETH_ADDRESS_IN_CONTRACTS: Address = Address::new([
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x01,
+]);
+
+/// Formal address of the bootloader on L2, used as a sender of the L2 -> L1 logs
+/// reporting the status of the priority operations.
+pub const BOOTLOADER_FORMAL_ADDRESS: Address = Address::new([
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x80, 0x01,
+]);
+
+/// Returns `true` if the L1 token address represents ETH.
+pub fn is_eth_l1_address(address: Address) -> bool {
+    address == ETHER_L1_ADDRESS || address == ETH_ADDRESS_IN_CONTRACTS
+}
+
 ///  Utility function that converts an L1 address to the L2 address.
 pub fn apply_l1_to_l2_alias(l1_address: Address) -> Address {
     let address_modulo: U256 = U256::from(2).pow(U256::from(160));
@@ -25,7 +48,9 @@ pub fn apply_l1_to_l2_alias(l1_address: Address) -> Address {
 
 #[cfg(test)]
 mod tests {
-    use super::apply_l1_to_l2_alias;
+    use super::{
+        ETH_ADDRESS_IN_CONTRACTS, ETHER_L1_ADDRESS, apply_l1_to_l2_alias, is_eth_l1_address,
+    };
     use alloy::primitives::address;
 
     #[tokio::test(flavor = "multi_thread")]
@@ -36,4 +61,13 @@ mod tests {
             address!("813A42B8205E5DedCd3374e5f4419843ADa77FFC")
         );
     }
+
+    #[test]
+    fn is_eth_l1_address_test() {
+        assert!(is_eth_l1_address(ETHER_L1_ADDRESS));
+        assert!(is_eth_l1_address(ETH_ADDRESS_IN_CONTRACTS));
+        assert!(!is_eth_l1_address(address!(
+            "702942B8205E5dEdCD3374E5f4419843adA76Eeb"
+        )));
+    }
 }
@@ -0,0 +1,58 @@
+//! Signers for smart contract accounts.
+
+use alloy::{
+    primitives::{Address, B256, Bytes},
+    signers::Signer,
+};
+
+/// Signer for a smart contract (custom) account.
+///
+/// Unlike EOAs, custom accounts validate transactions on their own, so the signature
+/// can be arbitrary data understood by the account contract (e.g. concatenated signatures
+/// of multisig owners, or a signature of a session key). The produced bytes are sent as
+/// [`custom_signature`](crate::network::unsigned_tx::eip712::Eip712Meta::custom_signature).
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+pub trait AccountSigner {
+    /// Address of the account contract.
+    fn address(&self) -> Address;
+
+    /// Produces the custom signature for the EIP-712 signature hash of the transaction.
+    async fn sign_hash(&self, hash: &B256) -> alloy::signers::Result<Bytes>;
+}
+
+/// Account signer for custom accounts that expect a single ECDSA signature of the owner,
+/// e.g. the default account implementation used in ZKsync examples.
+#[derive(Debug, Clone)]
+pub struct EcdsaAccountSigner<S> {
+    account: Address,
+    owner: S,
+}
+
+impl<S> EcdsaAccountSigner<S> {
+    /// Creates a signer for the `account` contract owned by `owner`.
+    pub fn new(account: Address, owner: S) -> Self {
+        Self { account, owner }
+    }
+
+    /// Returns the owner signer.
+    pub fn owner(&self) -> &S {
+        &self.owner
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl<S> AccountSigner for EcdsaAccountSigner<S>
+where
+    S: Signer + Send + Sync,
+{
+    fn address(&self) -> Address {
+        self.account
+    }
+
+    async fn sign_hash(&self, hash: &B256) -> alloy::signers::Result<Bytes> {
+        let signature = self.owner.sign_hash(hash).await?;
+        Ok(signature.as_bytes().into())
+    }
+}
@@ -4,23 +4,39 @@ use alloy::consensus::SignableTransaction;
 use alloy::network::{Network, NetworkWallet, TxSigner};
 use alloy::primitives::Address;
 
-use crate::network::{Zksync, tx_envelope::TxEnvelope, unsigned_tx::TypedTransaction};
+use crate::network::{
+    Zksync,
+    tx_envelope::{EMPTY_SIGNATURE, TxEnvelope},
+    unsigned_tx::TypedTransaction,
+};
 
 use alloy::primitives::Signature;
 use std::{collections::BTreeMap, sync::Arc};
 
+pub use self::{
+    account::{AccountSigner, EcdsaAccountSigner},
+    multisig::{MultisigAccountSigner, MultisigError},
+};
+
+mod account;
+mod multisig;
+
 /// A wallet capable of signing any transaction for the Ethereum network.
 #[derive(Clone, Default)]
 pub struct ZksyncWallet {
     default: Address,
+    default_account: Option<Address>,
     signers: BTreeMap<Address, Arc<dyn TxSigner<Signature> + Send + Sync>>,
+    accounts: BTreeMap<Address, Arc<dyn AccountSigner + Send + Sync>>,
 }
 
 impl std::fmt::Debug for ZksyncWallet {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("ZksyncWallet")
             .field("default_signer", &self.default)
+            .field("default_account", &self.default_account)
             .field("credentials", &self.signers.len())
+            .field("accounts", &self.accounts.len())
             .finish()
     }
 }
@@ -68,6 +84,7 @@ impl ZksyncWallet {
         S: TxSigner<Signature> + Send + Sync + 'static,
     {
         self.default = signer.address();
+        self.default_account = None;
         self.register_signer(signer);
     }
 
@@ -87,6 +104,44 @@ impl ZksyncWallet {
         self.signers.get(&address).cloned()
     }
 
+    /// Register a new signer for a custom account. This signer will be used to sign
+    /// EIP-712 transactions that specify the account address in the `from` field by
+    /// setting their custom signature.
+    ///
+    /// Custom accounts can only send EIP-712 transactions.
+    pub fn register_account_signer<S>(&mut self, signer: S)
+    where
+        S: AccountSigner + Send + Sync + 'static,
+    {
+        self.accounts.insert(signer.address(), Arc::new(signer));
+    }
+
+    /// Register a new signer for a custom account, and set the account as the default sender
+    /// of ZKsync transactions.
+    ///
+    /// Custom accounts cannot sign Ethereum transactions, so the default signer is still used
+    /// for them, e.g. as the L1 sender of deposits.
+    pub fn register_default_account_signer<S>(&mut self, signer: S)
+    where
+        S: AccountSigner + Send + Sync + 'static,
+    {
+        self.default_account = Some(signer.address());
+        self.register_account_signer(signer);
+    }
+
+    /// Get the address of the default custom account, if any.
+    pub fn default_account(&self) -> Option<Address> {
+        self.default_account
+    }
+
+    /// Get the custom account signer for the given address.
+    pub fn account_signer_by_address(
+        &self,
+        address: Address,
+    ) -> Option<Arc<dyn AccountSigner + Send + Sync + 'static>> {
+        self.accounts.get(&address).cloned()
+    }
+
     #[doc(alias = "sign_tx_inner")]
     async fn sign_transaction_inner(
         &self,
@@ -154,15 +209,15 @@ where
 
 impl NetworkWallet<Zksync> for ZksyncWallet {
     fn default_signer_address(&self) -> Address {
-        self.default
+        self.default_account.unwrap_or(self.default)
     }
 
     fn has_signer_for(&self, address: &Address) -> bool {
-        self.signers.contains_key(address)
+        self.signers.contains_key(address) || self.accounts.contains_key(address)
     }
 
     fn signer_addresses(&self) -> impl Iterator<Item = Address> {
-        self.signers.keys().copied()
+        self.signers.keys().chain(self.accounts.keys()).copied()
     }
 
     #[doc(alias = "sign_tx_from")]
@@ -171,6 +226,20 @@ impl NetworkWallet<Zksync> for ZksyncWallet {
         sender: Address,
         tx: TypedTransaction,
     ) -> alloy::signers::Result<TxEnvelope> {
+        if let Some(account) = self.account_signer_by_address(sender) {
+            let TypedTransaction::Eip712(mut t) = tx else {
+                return Err(alloy::signers::Error::other(format!(
+                    "Custom account {sender} can only sign EIP-712 transactions"
+                )));
+            };
+            let custom_signature = account.sign_hash(&t.signature_hash()).await?;
+            t.eip712_meta
+                .get_or_insert_with(Default::default)
+                .custom_signature = Some(custom_signature);
+            // The account validates the custom signature, so the ECDSA one is left empty.
+            return Ok(TxEnvelope::Eip712(t.into_signed(EMPTY_SIGNATURE)));
+        }
+
         match tx {
             TypedTransaction::Native(t) => {
                 let sig = <Self as NetworkWallet<alloy::network::Ethereum>>::sign_transaction_from(
@@ -183,6 +252,113 @@ impl NetworkWallet<Zksync> for ZksyncWallet {
                 let sig = self.sign_transaction_inner(sender, &mut t).await?;
                 Ok(TxEnvelope::Eip712(t.into_signed(sig)))
             }
+            TypedTransaction::L1Priority(_) | TypedTransaction::ProtocolUpgrade(_) => Err(
+                alloy::signers::Error::other("L1-originated transactions cannot be signed"),
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::unsigned_tx::eip712::{Eip712Meta, TxEip712};
+    use alloy::primitives::{U256, address};
+    use alloy::signers::local::PrivateKeySigner;
+
+    fn eip712_tx(from: Address) -> TxEip712 {
+        TxEip712 {
+            chain_id: 270,
+            from,
+            to: address!("82112600a140ceaa9d7da373bb65453f7d99af4b"),
+            nonce: U256::from(1),
+            value: U256::from(10),
+            gas: 300_000,
+            max_fee_per_gas: 250_000_000,
+            eip712_meta: Some(Eip712Meta {
+                gas_per_pubdata: U256::from(50_000),
+                ..Default::default()
+            }),
+            ..Default::default()
         }
     }
+
+    #[tokio::test]
+    async fn account_signer_sets_custom_signature() {
+        let account = address!("e30f4fb40666753a7596d315f2f1f1d140d1508b");
+        let owner = PrivateKeySigner::random();
+        let owner_address = owner.address();
+
+        let mut wallet = ZksyncWallet::new(PrivateKeySigner::random());
+        wallet.register_account_signer(EcdsaAccountSigner::new(account, owner));
+        assert!(NetworkWallet::<Zksync>::has_signer_for(&wallet, &account));
+
+        let tx = eip712_tx(account);
+        let signature_hash = tx.signature_hash();
+        let TxEnvelope::Eip712(signed) = NetworkWallet::<Zksync>::sign_transaction_from(
+            &wallet,
+            account,
+            TypedTransaction::Eip712(tx),
+        )
+        .await
+        .unwrap() else {
+            panic!("expected EIP-712 transaction");
+        };
+
+        let custom_signature = signed
+            .tx()
+            .eip712_meta
+            .as_ref()
+            .and_then(|meta| meta.custom_signature.clone())
+            .unwrap();
+        let recovered = Signature::try_from(custom_signature.as_ref())
+            .unwrap()
+            .recover_address_from_prehash(&signature_hash)
+            .unwrap();
+        assert_eq!(recovered, owner_address);
+        assert_eq!(*signed.hash(), signed.tx().tx_hash(signed.signature()));
+    }
+
+    #[tokio::test]
+    async fn account_signer_rejects_native_transactions() {
+        let account = address!("e30f4fb40666753a7596d315f2f1f1d140d1508b");
+        let mut wallet = ZksyncWallet::default();
+        wallet.register_default_account_signer(EcdsaAccountSigner::new(
+            account,
+            PrivateKeySigner::random(),
+        ));
+
+        let tx = alloy::consensus::TypedTransaction::Eip1559(Default::default());
+        let result = NetworkWallet::<Zksync>::sign_transaction_from(
+            &wallet,
+            account,
+            TypedTransaction::Native(tx),
+        )
+        .await;
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn default_account_does_not_replace_default_signer() {
+        let account = address!("e30f4fb40666753a7596d315f2f1f1d140d1508b");
+        let signer = PrivateKeySigner::random();
+        let signer_address = signer.address();
+
+        let mut wallet = ZksyncWallet::new(signer);
+        wallet.register_default_account_signer(EcdsaAccountSigner::new(
+            account,
+            PrivateKeySigner::random(),
+        ));
+        assert_eq!(wallet.default_account(), Some(account));
+        assert_eq!(wallet.default_signer().address(), signer_address);
+        assert_eq!(
+            NetworkWallet::<Zksync>::default_signer_address(&wallet),
+            account
+        );
+        // Ethereum transactions can't be sent by the account.
+        assert_eq!(
+            NetworkWallet::<alloy::network::Ethereum>::default_signer_address(&wallet),
+            signer_address
+        );
+    }
 }
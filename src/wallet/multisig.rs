@@ -0,0 +1,281 @@
+//! Signer for multisig custom accounts.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::Arc,
+};
+
+use alloy::{
+    primitives::{Address, B256, Bytes, Signature},
+    signers::Signer,
+};
+
+use super::AccountSigner;
+
+/// Errors that can occur while creating a [`MultisigAccountSigner`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum MultisigError {
+    /// At least one signature must be required.
+    #[error("Multisig threshold must be positive")]
+    ZeroThreshold,
+    /// More signatures are required than there are owners.
+    #[error("Multisig threshold {threshold} exceeds the number of owners {owners}")]
+    ThresholdExceedsOwners { threshold: usize, owners: usize },
+    /// Owner is listed more than once.
+    #[error("Multisig owner {0} is duplicated")]
+    DuplicateOwner(Address),
+}
+
+/// Signer for multisig custom accounts, e.g. the `TwoUserMultisig` account from the ZKsync
+/// examples.
+///
+/// The custom signature is the concatenation of `threshold` ECDSA signatures of the EIP-712
+/// signature hash, ordered the same way as the owners were provided to the signer. If the account
+/// contract expects a different order (e.g. by ascending addresses), the owners should be provided
+/// in that order.
+///
+/// Signatures can be produced by the locally available signers, or collected offline via
+/// [`MultisigAccountSigner::sign_partial`] and assembled with [`MultisigAccountSigner::combine`].
+#[derive(Clone)]
+pub struct MultisigAccountSigner {
+    account: Address,
+    owners: Vec<Address>,
+    threshold: usize,
+    signers: BTreeMap<Address, Arc<dyn Signer + Send + Sync>>,
+}
+
+impl std::fmt::Debug for MultisigAccountSigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MultisigAccountSigner")
+            .field("account", &self.account)
+            .field("owners", &self.owners)
+            .field("threshold", &self.threshold)
+            .field("signers", &self.signers.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl MultisigAccountSigner {
+    /// Creates a signer for the multisig `account` with the given owners, ordered the way
+    /// the account expects their signatures, and the number of required signatures.
+    ///
+    /// Returns an error if the threshold is zero or exceeds the number of owners, or if
+    /// the owners contain duplicates.
+    pub fn new(
+        account: Address,
+        owners: Vec<Address>,
+        threshold: usize,
+    ) -> Result<Self, MultisigError> {
+        if threshold == 0 {
+            return Err(MultisigError::ZeroThreshold);
+        }
+        if threshold > owners.len() {
+            return Err(MultisigError::ThresholdExceedsOwners {
+                threshold,
+                owners: owners.len(),
+            });
+        }
+        let mut unique_owners = BTreeSet::new();
+        if let Some(owner) = owners.iter().find(|owner| !unique_owners.insert(*owner)) {
+            return Err(MultisigError::DuplicateOwner(*owner));
+        }
+        Ok(Self {
+            account,
+            owners,
+            threshold,
+            signers: BTreeMap::new(),
+        })
+    }
+
+    /// Adds a local signer for one of the owners.
+    pub fn with_signer<S>(mut self, signer: S) -> Self
+    where
+        S: Signer + Send + Sync + 'static,
+    {
+        self.signers.insert(signer.address(), Arc::new(signer));
+        self
+    }
+
+    /// Returns the owners of the account.
+    pub fn owners(&self) -> &[Address] {
+        &self.owners
+    }
+
+    /// Returns the number of signatures required by the account.
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    /// Signs the hash with every local signer that belongs to an owner.
+    ///
+    /// The returned signatures can be shared with other owners and passed to
+    /// [`Self::combine`] along with the signatures collected elsewhere.
+    pub async fn sign_partial(&self, hash: &B256) -> alloy::signers::Result<Vec<Signature>> {
+        let mut signatures = Vec::with_capacity(self.signers.len());
+        for owner in &self.owners {
+            if let Some(signer) = self.signers.get(owner) {
+                signatures.push(signer.sign_hash(hash).await?);
+            }
+        }
+        Ok(signatures)
+    }
+
+    /// Combines signatures of the hash into the custom signature expected by the account.
+    ///
+    /// Signers are recovered from the signatures, so the signatures can be provided in any order.
+    /// Duplicates are ignored. Returns an error if any signature doesn't belong to an owner, or if
+    /// there are fewer signatures than the threshold.
+    pub fn combine(
+        &self,
+        hash: &B256,
+        signatures: impl IntoIterator<Item = Signature>,
+    ) -> alloy::signers::Result<Bytes> {
+        let mut by_owner = BTreeMap::new();
+        for signature in signatures {
+            let signer = signature.recover_address_from_prehash(hash)?;
+            if !self.owners.contains(&signer) {
+                return Err(alloy::signers::Error::other(format!(
+                    "{signer} is not an owner of the multisig account {}",
+                    self.account
+                )));
+            }
+            by_owner.insert(signer, signature);
+        }
+
+        if by_owner.len() < self.threshold {
+            return Err(alloy::signers::Error::other(format!(
+                "Not enough signatures for the multisig account {}: got {}, required {}",
+                self.account,
+                by_owner.len(),
+                self.threshold
+            )));
+        }
+
+        let custom_signature = self
+            .owners
+            .iter()
+            .filter_map(|owner| by_owner.get(owner))
+            .take(self.threshold)
+            .flat_map(|signature| signature.as_bytes())
+            .collect::<Vec<_>>();
+        Ok(custom_signature.into())
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl AccountSigner for MultisigAccountSigner {
+    fn address(&self) -> Address {
+        self.account
+    }
+
+    async fn sign_hash(&self, hash: &B256) -> alloy::signers::Result<Bytes> {
+        let signatures = self.sign_partial(hash).await?;
+        self.combine(hash, signatures)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::{primitives::address, signers::local::PrivateKeySigner};
+
+    const ACCOUNT: Address = address!("e30f4fb40666753a7596d315f2f1f1d140d1508b");
+
+    fn owners() -> Vec<PrivateKeySigner> {
+        (0..3).map(|_| PrivateKeySigner::random()).collect()
+    }
+
+    #[tokio::test]
+    async fn signs_with_local_signers_in_owner_order() {
+        let owners = owners();
+        let addresses = owners.iter().map(|s| s.address()).collect::<Vec<_>>();
+        let signer = MultisigAccountSigner::new(ACCOUNT, addresses.clone(), 2)
+            .unwrap()
+            .with_signer(owners[2].clone())
+            .with_signer(owners[0].clone());
+
+        let hash = B256::repeat_byte(0x42);
+        let custom_signature = AccountSigner::sign_hash(&signer, &hash).await.unwrap();
+        assert_eq!(custom_signature.len(), 130);
+
+        let recovered = custom_signature
+            .chunks(65)
+            .map(|chunk| {
+                Signature::try_from(chunk)
+                    .unwrap()
+                    .recover_address_from_prehash(&hash)
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(recovered, vec![addresses[0], addresses[2]]);
+    }
+
+    #[tokio::test]
+    async fn combines_partial_signatures() {
+        let owners = owners();
+        let addresses = owners.iter().map(|s| s.address()).collect::<Vec<_>>();
+        let hash = B256::repeat_byte(0x42);
+
+        let first = MultisigAccountSigner::new(ACCOUNT, addresses.clone(), 2)
+            .unwrap()
+            .with_signer(owners[1].clone());
+        let second = MultisigAccountSigner::new(ACCOUNT, addresses.clone(), 2)
+            .unwrap()
+            .with_signer(owners[0].clone());
+
+        let mut signatures = first.sign_partial(&hash).await.unwrap();
+        assert!(first.combine(&hash, signatures.clone()).is_err());
+
+        signatures.extend(second.sign_partial(&hash).await.unwrap());
+        let combined = first.combine(&hash, signatures).unwrap();
+
+        let expected = MultisigAccountSigner::new(ACCOUNT, addresses, 2)
+            .unwrap()
+            .with_signer(owners[0].clone())
+            .with_signer(owners[1].clone());
+        assert_eq!(
+            combined,
+            AccountSigner::sign_hash(&expected, &hash).await.unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_signatures_of_non_owners() {
+        let owners = owners();
+        let addresses = owners.iter().map(|s| s.address()).collect::<Vec<_>>();
+        let signer = MultisigAccountSigner::new(ACCOUNT, addresses, 1).unwrap();
+
+        let hash = B256::repeat_byte(0x42);
+        let stranger = PrivateKeySigner::random().sign_hash(&hash).await.unwrap();
+        assert!(signer.combine(&hash, [stranger]).is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_owners_and_threshold() {
+        let addresses: Vec<_> = owners().iter().map(|s| s.address()).collect();
+        assert_eq!(
+            MultisigAccountSigner::new(ACCOUNT, addresses.clone(), 0).unwrap_err(),
+            MultisigError::ZeroThreshold
+        );
+        assert_eq!(
+            MultisigAccountSigner::new(ACCOUNT, addresses.clone(), 4).unwrap_err(),
+            MultisigError::ThresholdExceedsOwners {
+                threshold: 4,
+                owners: 3
+            }
+        );
+        assert_eq!(
+            MultisigAccountSigner::new(ACCOUNT, vec![], 1).unwrap_err(),
+            MultisigError::ThresholdExceedsOwners {
+                threshold: 1,
+                owners: 0
+            }
+        );
+        let duplicated = vec![addresses[0], addresses[1], addresses[0]];
+        assert_eq!(
+            MultisigAccountSigner::new(ACCOUNT, duplicated, 2).unwrap_err(),
+            MultisigError::DuplicateOwner(addresses[0])
+        );
+    }
+}